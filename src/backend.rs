@@ -0,0 +1,1099 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use anyhow::bail;
+use serde_with::DeserializeFromStr;
+
+use crate::{CommandAttribute, Expression, Highlight, Plugin, Value};
+
+/// The language the generated files are written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, DeserializeFromStr)]
+pub enum Backend {
+    Vim,
+    Lua,
+}
+
+impl FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "vim" | "vimscript" => Backend::Vim,
+            "lua" => Backend::Lua,
+            _ => bail!("Unsupported backend: `{}` (supported: vim, lua)", s),
+        })
+    }
+}
+
+/// The body of an autocommand, either an ex command or a lua chunk, or the rhs of a mapping,
+/// either keys or a lua function.
+pub enum Body {
+    Cmd(String),
+    Lua(String),
+}
+
+/// Modifiers of a mapping.
+#[derive(Clone, Default)]
+pub struct MapOptions {
+    pub recursive: bool,
+    pub silent: bool,
+    /// The rhs is an expression evaluating to the keys to press.
+    pub expr: bool,
+    /// Only map in the current buffer.
+    pub buffer: bool,
+    /// Do not wait for longer mappings starting with the lhs.
+    pub nowait: bool,
+    /// Only remap keys with mappings defined in the same script.
+    pub script: bool,
+    /// Fail if the lhs is already mapped.
+    pub unique: bool,
+    /// Shown by e.g. which-key, only supported by the lua backend.
+    pub desc: Option<String>,
+}
+
+impl MapOptions {
+    pub(crate) fn enable(&mut self, option: MapOption) {
+        match option {
+            MapOption::Buffer => self.buffer = true,
+            MapOption::Nowait => self.nowait = true,
+            MapOption::Silent => self.silent = true,
+            MapOption::Script => self.script = true,
+            MapOption::Expr => self.expr = true,
+            MapOption::Unique => self.unique = true,
+        }
+    }
+
+    pub(crate) fn contains(&self, option: MapOption) -> bool {
+        match option {
+            MapOption::Buffer => self.buffer,
+            MapOption::Nowait => self.nowait,
+            MapOption::Silent => self.silent,
+            MapOption::Script => self.script,
+            MapOption::Expr => self.expr,
+            MapOption::Unique => self.unique,
+        }
+    }
+}
+
+/// A modifier of a mapping given by name in its `opts`, see `:h :map-arguments`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapOption {
+    Buffer,
+    Nowait,
+    Silent,
+    Script,
+    Expr,
+    Unique,
+}
+
+impl MapOption {
+    /// Every option, in the order vimscript needs their arguments.
+    const ALL: [MapOption; 6] = [
+        MapOption::Buffer,
+        MapOption::Nowait,
+        MapOption::Silent,
+        MapOption::Script,
+        MapOption::Expr,
+        MapOption::Unique,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            MapOption::Buffer => "buffer",
+            MapOption::Nowait => "nowait",
+            MapOption::Silent => "silent",
+            MapOption::Script => "script",
+            MapOption::Expr => "expr",
+            MapOption::Unique => "unique",
+        }
+    }
+
+    /// The argument of `:map`, e.g. `<silent>`.
+    fn argument(self) -> String {
+        format!("<{}>", self.name())
+    }
+}
+
+impl FromStr for MapOption {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Also accept them written like in vimscript, e.g. `<unique>`
+        let name = s.trim_start_matches('<').trim_end_matches('>');
+        match MapOption::ALL
+            .iter()
+            .find(|option| option.name().eq_ignore_ascii_case(name))
+        {
+            Some(option) => Ok(*option),
+            None => bail!(
+                "Unsupported mapping option: `{}` (supported: {})",
+                s,
+                MapOption::ALL.map(MapOption::name).join(", ")
+            ),
+        }
+    }
+}
+
+/// Modifiers of an autocommand.
+pub struct AutoCommandOptions<'a> {
+    pub silent: bool,
+    /// Remove the autocommand after it ran once (`++once`).
+    pub once: bool,
+    /// Allow the autocommand to trigger other autocommands (`++nested`).
+    pub nested: bool,
+    /// Only run the body if all entries match `v:event`.
+    pub event: &'a BTreeMap<String, String>,
+    /// Description shown in `:autocmd`, only supported by lua, so vimscript gets a comment.
+    pub desc: Option<&'a str>,
+}
+
+impl Backend {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Backend::Vim => "vim",
+            Backend::Lua => "lua",
+        }
+    }
+
+    pub fn comment(self, text: &str) -> String {
+        let leader = match self {
+            Backend::Vim => "\"",
+            Backend::Lua => "--",
+        };
+        // Every line needs its own leader, otherwise multi-line text would end up as code
+        text.trim_end()
+            .split('\n')
+            .map(|line| format!("{} {}", leader, line).trim_end().to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Maps `lhs` to `rhs` in every mode of `modes` (given as their mode letters).
+    pub(crate) fn map(
+        self,
+        modes: &[char],
+        lhs: &str,
+        rhs: Body,
+        options: &MapOptions,
+    ) -> Vec<String> {
+        match self {
+            Backend::Vim => {
+                let cmd = if options.recursive { "map" } else { "noremap" };
+                let mut arguments: String = MapOption::ALL
+                    .iter()
+                    .filter(|option| options.contains(**option))
+                    .map(|option| option.argument())
+                    .collect();
+                if !arguments.is_empty() {
+                    arguments.push(' ');
+                }
+                let rhs = match rhs {
+                    Body::Cmd(keys) => keys,
+                    // The parentheses allow calling inline `function() ... end` as well
+                    Body::Lua(function) => format!("<CMD>lua ({})()<CR>", function),
+                };
+                // A bar would end the command, any other key notation like `<C-\><C-n>` is kept
+                let rhs = rhs.replace('|', r"\|");
+                modes
+                    .iter()
+                    .map(|mode| format!("{}{} {}{} {}", mode, cmd, arguments, lhs, rhs))
+                    .collect()
+            }
+            Backend::Lua => {
+                let mut opts = vec![];
+                if options.buffer {
+                    opts.push("buffer = true".to_string());
+                }
+                if options.nowait {
+                    opts.push("nowait = true".to_string());
+                }
+                if options.silent {
+                    opts.push("silent = true".to_string());
+                }
+                if options.script {
+                    opts.push("script = true".to_string());
+                }
+                if options.recursive {
+                    opts.push("remap = true".to_string());
+                }
+                if options.expr {
+                    opts.push("expr = true".to_string());
+                }
+                if options.unique {
+                    opts.push("unique = true".to_string());
+                }
+                if let Some(desc) = &options.desc {
+                    opts.push(format!("desc = {}", lua_string(desc)));
+                }
+                vec![format!(
+                    "vim.keymap.set({}, {}, {}, {})",
+                    lua_list(modes.iter().map(|mode| lua_string(&mode.to_string()))),
+                    lua_string(lhs),
+                    match rhs {
+                        Body::Cmd(keys) => lua_string(&keys),
+                        Body::Lua(function) => function,
+                    },
+                    lua_list(opts.into_iter())
+                )]
+            }
+        }
+    }
+
+    /// Pads the columns up to the rhs of every contiguous block of mappings in `text` (lines
+    /// generated by [`Backend::map`] with the same indentation) to a common width, so the lhs
+    /// and rhs line up.
+    pub(crate) fn align_mappings(self, text: &str) -> String {
+        let mut aligned = Vec::new();
+        let mut block: Vec<MapColumns> = Vec::new();
+        for line in text.split('\n') {
+            match self.map_columns(line) {
+                Some(columns)
+                    if block
+                        .first()
+                        .is_none_or(|first| first.indent == columns.indent) =>
+                {
+                    block.push(columns)
+                }
+                columns => {
+                    aligned.extend(align_block(&block));
+                    block.clear();
+                    match columns {
+                        Some(columns) => block.push(columns),
+                        None => aligned.push(line.to_string()),
+                    }
+                }
+            }
+        }
+        aligned.extend(align_block(&block));
+        aligned.join("\n")
+    }
+
+    /// Splits a `line` generated by [`Backend::map`] into the columns to align and the rest.
+    fn map_columns(self, line: &str) -> Option<MapColumns<'_>> {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
+        match self {
+            Backend::Vim => {
+                // `{mode}map` or `{mode}noremap`, followed by the arguments, the lhs and the rhs
+                let (cmd, rest) = trimmed.split_once(' ')?;
+                let mode = cmd.strip_suffix("map")?;
+                let mode = mode.strip_suffix("nore").unwrap_or(mode);
+                if mode.len() > 1 || !mode.chars().all(|c| c.is_ascii_lowercase()) {
+                    return None;
+                }
+                let (arguments, rest) = match rest.split_once(' ') {
+                    Some((arguments, rest))
+                        if arguments.starts_with('<')
+                            && arguments.split_inclusive('>').all(|argument| {
+                                MapOption::ALL
+                                    .iter()
+                                    .any(|option| option.argument() == argument)
+                            }) =>
+                    {
+                        (arguments, rest)
+                    }
+                    _ => ("", rest),
+                };
+                let (lhs, rhs) = rest.split_once(' ')?;
+                Some(MapColumns {
+                    indent,
+                    columns: vec![cmd, arguments, lhs],
+                    rest: rhs,
+                })
+            }
+            Backend::Lua => {
+                // `vim.keymap.set({ modes }, "lhs", rhs, opts)`
+                let modes_end = trimmed.strip_prefix("vim.keymap.set({")?.find("}, ")?
+                    + "vim.keymap.set({},".len();
+                let rest = &trimmed[modes_end + 1..];
+                let lhs_end = lua_string_len(rest)? + 1;
+                if !rest[lhs_end..].starts_with(' ') {
+                    return None;
+                }
+                Some(MapColumns {
+                    indent,
+                    columns: vec![&trimmed[..modes_end], &rest[..lhs_end]],
+                    rest: &rest[lhs_end + 1..],
+                })
+            }
+        }
+    }
+
+    /// Registers the labels of prefixes (lhs, label) with which-key, if it is installed. Only
+    /// supported by the lua backend, as which-key is configured in lua.
+    pub(crate) fn which_key_groups(
+        self,
+        groups: &[(String, String)],
+        modes: &[char],
+        buffer: bool,
+    ) -> Option<String> {
+        if self == Backend::Vim || groups.is_empty() {
+            return None;
+        }
+        let modes = lua_list(modes.iter().map(|mode| lua_string(&mode.to_string())));
+        let buffer = if buffer { ", buffer = 0" } else { "" };
+        let groups: Vec<_> = groups
+            .iter()
+            .map(|(lhs, label)| {
+                format!(
+                    "    {{ {}, group = {}, mode = {}{} }},",
+                    lua_string(lhs),
+                    lua_string(label),
+                    modes,
+                    buffer
+                )
+            })
+            .collect();
+        Some(format!(
+            "pcall(function()\n  require(\"which-key\").add({{\n{}\n  }})\nend)",
+            groups.join("\n")
+        ))
+    }
+
+    /// Abbreviates `lhs` to `rhs` in every mode of `modes` (`i` or `c`), only `recursive` and
+    /// `buffer` of `options` are supported.
+    pub(crate) fn abbreviate(
+        self,
+        modes: &[char],
+        lhs: &str,
+        rhs: &str,
+        options: &MapOptions,
+    ) -> Vec<String> {
+        match self {
+            Backend::Vim => {
+                let cmd = if options.recursive {
+                    "abbrev"
+                } else {
+                    "noreabbrev"
+                };
+                let mut arguments = String::new();
+                if options.buffer {
+                    arguments.push_str("<buffer> ");
+                }
+                if options.expr {
+                    arguments.push_str("<expr> ");
+                }
+                let rhs = rhs.replace('|', r"\|");
+                modes
+                    .iter()
+                    .map(|mode| format!("{}{} {}{} {}", mode, cmd, arguments, lhs, rhs))
+                    .collect()
+            }
+            Backend::Lua => {
+                let mut opts = vec![];
+                if options.buffer {
+                    opts.push("buffer = true".to_string());
+                }
+                if options.recursive {
+                    opts.push("remap = true".to_string());
+                }
+                if options.expr {
+                    opts.push("expr = true".to_string());
+                }
+                vec![format!(
+                    "vim.keymap.set({}, {}, {}, {})",
+                    lua_list(modes.iter().map(|mode| lua_string(&format!("{}a", mode)))),
+                    lua_string(lhs),
+                    lua_string(rhs),
+                    lua_list(opts.into_iter())
+                )]
+            }
+        }
+    }
+
+    /// Opens an augroup, `clear` removes autocommands added to it earlier.
+    pub(crate) fn augroup(self, name: &str, clear: bool) -> Vec<String> {
+        match self {
+            Backend::Vim => {
+                let mut lines = vec![format!("augroup {}", name)];
+                if clear {
+                    lines.push("autocmd!".to_string());
+                }
+                lines
+            }
+            Backend::Lua => vec![format!(
+                "vim.api.nvim_create_augroup({}, {{ clear = {} }})",
+                lua_string(name),
+                clear
+            )],
+        }
+    }
+
+    pub(crate) fn augroup_end(self) -> Option<String> {
+        match self {
+            Backend::Vim => Some("augroup END".to_string()),
+            Backend::Lua => None,
+        }
+    }
+
+    /// An expression checking `has()` features, given as alternatives of features that all need
+    /// to be present (`true`) or absent (`false`).
+    pub(crate) fn has(self, alternatives: &[Vec<(String, bool)>]) -> String {
+        let (and, or) = match self {
+            Backend::Vim => (" && ", " || "),
+            Backend::Lua => (" and ", " or "),
+        };
+        let alternatives: Vec<_> = alternatives
+            .iter()
+            .map(|features| {
+                let checks: Vec<_> = features
+                    .iter()
+                    .map(|(feature, present)| match self {
+                        Backend::Vim => {
+                            format!(
+                                "{}has({})",
+                                if *present { "" } else { "!" },
+                                vim_string(feature)
+                            )
+                        }
+                        Backend::Lua => format!(
+                            "vim.fn.has({}) == {}",
+                            lua_string(feature),
+                            if *present { 1 } else { 0 }
+                        ),
+                    })
+                    .collect();
+                if alternatives.len() > 1 && checks.len() > 1 {
+                    format!("({})", checks.join(and))
+                } else {
+                    checks.join(and)
+                }
+            })
+            .collect();
+        if alternatives.len() > 1 {
+            format!("({})", alternatives.join(or))
+        } else {
+            alternatives.join(or)
+        }
+    }
+
+    /// Only runs `lines` if all `conditions` (see [`Backend::has`]) hold.
+    pub(crate) fn guard(self, conditions: &[String], lines: Vec<String>) -> Vec<String> {
+        match self {
+            // Not indented, as the end marker of a heredoc has to start the line
+            Backend::Vim => {
+                let mut guarded = vec![format!("if {}", conditions.join(" && "))];
+                guarded.extend(lines);
+                guarded.push("endif".to_string());
+                guarded
+            }
+            Backend::Lua => {
+                let mut guarded = vec![format!("if {} then", conditions.join(" and "))];
+                guarded.extend(lines.iter().map(|line| indent(line, 1)));
+                guarded.push("end".to_string());
+                guarded
+            }
+        }
+    }
+
+    /// An expression checking that the lua module `plugin` can be required.
+    pub(crate) fn plugin_available(self, plugin: &str) -> String {
+        let check = format!("pcall(require, {})", lua_string(plugin));
+        match self {
+            Backend::Vim => format!("luaeval({})", vim_string(&check)),
+            Backend::Lua => check,
+        }
+    }
+
+    /// An autocommand inside the augroup `group`. `index` has to be unique across all autocommands, it names the
+    /// helpers needed for multi-line lua bodies in vimscript.
+    pub(crate) fn autocmd(
+        self,
+        index: usize,
+        group: &str,
+        triggers: &[String],
+        patterns: &[String],
+        body: Body,
+        options: &AutoCommandOptions,
+    ) -> String {
+        let event = options.event;
+        match self {
+            Backend::Vim => {
+                let mut parts = vec![
+                    "autocmd".to_string(),
+                    triggers.join(","),
+                    patterns
+                        .iter()
+                        .map(|pattern| vim_pattern(pattern))
+                        .collect::<Vec<_>>()
+                        .join(","),
+                ];
+                if options.once {
+                    parts.push("++once".to_string());
+                }
+                if options.nested {
+                    parts.push("++nested".to_string());
+                }
+                if options.silent {
+                    parts.push("silent!".to_string());
+                }
+                let mut heredoc = None;
+                let cmd = match body {
+                    Body::Cmd(cmd) => cmd,
+                    // `lua << EOF` cannot be used in an autocmd directly, so the body is
+                    // defined as a function up front
+                    Body::Lua(lua) if lua.contains('\n') => {
+                        let (definition, function) =
+                            lua_function("nvim_config_autocmds", &index.to_string(), "", &lua);
+                        heredoc = Some(definition);
+                        format!("lua {}()", function)
+                    }
+                    Body::Lua(lua) => format!("lua {}", lua),
+                };
+                if event.is_empty() {
+                    parts.push(cmd);
+                } else {
+                    let condition = event
+                        .iter()
+                        .map(|(key, value)| format!("v:event.{} is {}", key, vim_string(value)))
+                        .collect::<Vec<_>>()
+                        .join(" && ");
+                    // `execute` runs the command unchanged, so quotes and `<afile>` survive
+                    parts.push(format!(
+                        "if {} | execute {} | endif",
+                        condition,
+                        vim_string(&cmd)
+                    ));
+                }
+                let mut lines: Vec<_> = options
+                    .desc
+                    .map(|desc| self.comment(desc))
+                    .into_iter()
+                    .collect();
+                lines.extend(heredoc);
+                lines.push(parts.join(" "));
+                lines.join("\n")
+            }
+            Backend::Lua => {
+                let mut lines = vec![
+                    format!(
+                        "vim.api.nvim_create_autocmd({}, {{",
+                        lua_list(triggers.iter().map(|trigger| lua_string(trigger)))
+                    ),
+                    format!("  group = {},", lua_string(group)),
+                ];
+                if patterns == ["<buffer>"] {
+                    lines.push("  buffer = 0,".to_string());
+                } else {
+                    lines.push(format!(
+                        "  pattern = {},",
+                        lua_list(
+                            patterns
+                                .iter()
+                                .flat_map(|pattern| pattern.split(','))
+                                .map(lua_string)
+                        )
+                    ));
+                }
+                if options.once {
+                    lines.push("  once = true,".to_string());
+                }
+                if options.nested {
+                    lines.push("  nested = true,".to_string());
+                }
+                if let Some(desc) = options.desc {
+                    lines.push(format!("  desc = {},", lua_string(desc)));
+                }
+                if event.is_empty() && !options.silent {
+                    match body {
+                        Body::Cmd(cmd) => lines.push(format!("  command = {},", lua_string(&cmd))),
+                        Body::Lua(lua) => {
+                            lines.push("  callback = function()".to_string());
+                            lines.push(indent(&lua, 2));
+                            lines.push("  end,".to_string());
+                        }
+                    }
+                } else {
+                    let mut body = match body {
+                        Body::Cmd(cmd) => format!("vim.cmd({})", lua_string(&cmd)),
+                        Body::Lua(lua) => lua,
+                    };
+                    if options.silent {
+                        body = format!("pcall(function()\n{}\nend)", indent(&body, 1));
+                    }
+                    if !event.is_empty() {
+                        let condition = event
+                            .iter()
+                            .map(|(key, value)| {
+                                format!("vim.v.event.{} == {}", key, lua_string(value))
+                            })
+                            .collect::<Vec<_>>()
+                            .join(" and ");
+                        body = format!("if {} then\n{}\nend", condition, indent(&body, 1));
+                    }
+                    lines.push("  callback = function()".to_string());
+                    lines.push(indent(&body, 2));
+                    lines.push("  end,".to_string());
+                }
+                lines.push("})".to_string());
+                lines.join("\n")
+            }
+        }
+    }
+
+    /// Defines the user command `name`.
+    pub(crate) fn command(
+        self,
+        name: &str,
+        body: Body,
+        attributes: &[(&str, &CommandAttribute)],
+    ) -> String {
+        match self {
+            Backend::Vim => {
+                let mut parts = vec!["command!".to_string()];
+                for (attribute, value) in attributes {
+                    match value {
+                        CommandAttribute::Bool(false) => {}
+                        CommandAttribute::Bool(true) => parts.push(format!("-{}", attribute)),
+                        value => parts.push(format!("-{}={}", attribute, value)),
+                    }
+                }
+                parts.push(name.to_string());
+                match body {
+                    Body::Cmd(cmd) => parts.push(cmd),
+                    // Like with `nvim_create_user_command` the body can access the arguments
+                    // through `opts`
+                    Body::Lua(lua) => {
+                        let (definition, function) =
+                            lua_function("nvim_config_commands", &lua_string(name), "opts", &lua);
+                        parts.push(format!(
+                            "lua {}({{ args = <q-args>, fargs = {{ <f-args> }}, \
+                             bang = <q-bang> == \"!\", line1 = <line1>, line2 = <line2>, \
+                             range = <range>, count = <count>, mods = <q-mods> }})",
+                            function
+                        ));
+                        return format!("{}\n{}", definition, parts.join(" "));
+                    }
+                }
+                parts.join(" ")
+            }
+            Backend::Lua => {
+                let body = match body {
+                    Body::Cmd(cmd) => lua_string(&cmd),
+                    Body::Lua(lua) => format!("function(opts)\n{}\nend", indent(&lua, 1)),
+                };
+                let attributes = attributes
+                    .iter()
+                    .filter(|(_, value)| !matches!(value, CommandAttribute::Bool(false)))
+                    .map(|(attribute, value)| {
+                        let value = match value {
+                            CommandAttribute::Bool(value) => value.to_string(),
+                            CommandAttribute::Int(value) => value.to_string(),
+                            CommandAttribute::String(value) => lua_string(value),
+                        };
+                        format!("{} = {}", attribute, value)
+                    });
+                format!(
+                    "vim.api.nvim_create_user_command({}, {}, {})",
+                    lua_string(name),
+                    body,
+                    lua_list(attributes)
+                )
+            }
+        }
+    }
+
+    /// A raw `set` argument like `number` or `tabstop=4`.
+    pub(crate) fn set(self, local: bool, option: &str) -> String {
+        let cmd = if local { "setlocal" } else { "set" };
+        match self {
+            Backend::Vim => format!("{} {}", cmd, option),
+            Backend::Lua => format!("vim.cmd({})", lua_string(&format!("{} {}", cmd, option))),
+        }
+    }
+
+    /// Boolean options are toggled (`set wrap`/`set nowrap`) instead of assigned.
+    pub(crate) fn set_value(self, local: bool, name: &str, value: &Value) -> String {
+        let escaped;
+        let value = match value {
+            // A comma would split an item of a list option, like an `errorformat` pattern
+            Value::List(values) => {
+                escaped = Value::List(
+                    values
+                        .iter()
+                        .map(|value| match value {
+                            Value::String(value) => Value::String(value.replace(',', r"\,")),
+                            value => value.clone(),
+                        })
+                        .collect(),
+                );
+                &escaped
+            }
+            value => value,
+        };
+        match self {
+            Backend::Vim => {
+                let cmd = if local { "setlocal" } else { "set" };
+                match value {
+                    Value::Bool(true) => format!("{} {}", cmd, name),
+                    Value::Bool(false) => format!("{} no{}", cmd, name),
+                    // `set` only takes literal values
+                    Value::Expr(Expression { expr }) => {
+                        let scope = if local { "l:" } else { "" };
+                        format!("let &{}{}={}", scope, name, expr)
+                    }
+                    Value::List(values) => format!(
+                        "{} {}={}",
+                        cmd,
+                        name,
+                        values
+                            .iter()
+                            .map(|value| match value {
+                                Value::String(value) => set_string(value),
+                                value => value.to_string(),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    ),
+                    // Like `listchars`, e.g. `tab:>\ ,trail:-`
+                    Value::Map(entries) => format!(
+                        "{} {}={}",
+                        cmd,
+                        name,
+                        entries
+                            .iter()
+                            .map(|(key, value)| match value {
+                                Value::String(value) => format!("{}:{}", key, set_string(value)),
+                                value => format!("{}:{}", key, value),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    ),
+                    // Unlike in `let`, strings are not quoted
+                    Value::String(value) => format!("{} {}={}", cmd, name, set_string(value)),
+                    value => format!("{} {}={}", cmd, name, value),
+                }
+            }
+            Backend::Lua => {
+                let opt = if local { "vim.opt_local" } else { "vim.opt" };
+                format!("{}.{} = {}", opt, name, lua_value(value))
+            }
+        }
+    }
+
+    /// Assigns a variable, `name` may carry a scope like `g:`.
+    pub(crate) fn r#let(self, name: &str, value: &Value) -> String {
+        match self {
+            Backend::Vim => format!("let {}={}", name, value),
+            Backend::Lua => {
+                let (scope, name) = match name.split_once(':') {
+                    Some((scope, name)) => (scope, name),
+                    // Unscoped variables outside of functions are global
+                    None => ("g", name),
+                };
+                format!("vim.{}{} = {}", scope, lua_index(name), lua_value(value))
+            }
+        }
+    }
+
+    /// Sets the variable `name` to `value` taken literally, unlike the strings of [`Self::r#let`],
+    /// which vimscript interprets escapes in.
+    pub(crate) fn let_literal(self, name: &str, value: &str) -> String {
+        match self {
+            Backend::Vim => format!("let {}={}", name, vim_string(value)),
+            Backend::Lua => self.r#let(name, &Value::String(value.to_string())),
+        }
+    }
+
+    /// Removes the variable `name`, without failing if it does not exist.
+    pub(crate) fn unlet(self, name: &str) -> String {
+        match self {
+            Backend::Vim => format!("unlet! {}", name),
+            Backend::Lua => {
+                let (scope, name) = name.split_once(':').unwrap_or(("g", name));
+                format!("vim.{}{} = nil", scope, lua_index(name))
+            }
+        }
+    }
+
+    /// Includes the file `path` verbatim, lua files (by their extension) are run through
+    /// `lua << EOF` by the vim backend and other files through `vim.cmd()` by the lua backend.
+    pub(crate) fn source(self, path: &str, content: &str) -> String {
+        let content = content.trim_end();
+        let lua = path.to_ascii_lowercase().ends_with(".lua");
+        match (self, lua) {
+            (Backend::Vim, false) | (Backend::Lua, true) => content.to_string(),
+            (Backend::Vim, true) => {
+                let marker = heredoc_marker(content);
+                format!("lua << {marker}\n{}\n{marker}", content, marker = marker)
+            }
+            (Backend::Lua, false) => {
+                // A long bracket string, with enough `=` to not be closed by the content
+                let mut level = String::new();
+                while content.contains(&format!("]{}]", level)) {
+                    level.push('=');
+                }
+                format!("vim.cmd([{level}[\n{}\n]{level}])", content, level = level)
+            }
+        }
+    }
+
+    /// Defines the highlight group `name`, an empty `highlight` clears it.
+    pub(crate) fn highlight(self, name: &str, highlight: &Highlight) -> String {
+        let styles: Vec<_> = [
+            (highlight.bold, "bold"),
+            (highlight.italic, "italic"),
+            (highlight.underline, "underline"),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, style)| *style)
+        .collect();
+        match self {
+            Backend::Vim => {
+                if let Some(link) = &highlight.link {
+                    return format!("highlight link {} {}", name, link);
+                }
+                let mut attributes = Vec::new();
+                if let Some(fg) = &highlight.fg {
+                    attributes.push(format!("guifg={}", fg));
+                }
+                if let Some(bg) = &highlight.bg {
+                    attributes.push(format!("guibg={}", bg));
+                }
+                if !styles.is_empty() {
+                    attributes.push(format!("gui={}", styles.join(",")));
+                }
+                if attributes.is_empty() {
+                    format!("highlight clear {}", name)
+                } else {
+                    format!("highlight {} {}", name, attributes.join(" "))
+                }
+            }
+            Backend::Lua => {
+                let mut attributes = Vec::new();
+                if let Some(link) = &highlight.link {
+                    attributes.push(format!("link = {}", lua_string(link)));
+                }
+                if let Some(fg) = &highlight.fg {
+                    attributes.push(format!("fg = {}", lua_string(fg)));
+                }
+                if let Some(bg) = &highlight.bg {
+                    attributes.push(format!("bg = {}", lua_string(bg)));
+                }
+                attributes.extend(styles.iter().map(|style| format!("{} = true", style)));
+                format!(
+                    "vim.api.nvim_set_hl(0, {}, {})",
+                    lua_string(name),
+                    lua_list(attributes.into_iter())
+                )
+            }
+        }
+    }
+}
+
+/// A plugin spec for lazy.nvim, an entry of the table returned by a `lua/plugins/*.lua` file.
+pub(crate) fn lazy_spec(plugin: &Plugin) -> String {
+    let strings = |values: &[String]| lua_list(values.iter().map(|value| lua_string(value)));
+    let mut fields = vec![lua_string(&plugin.repo)];
+    if let Some(lazy) = plugin.lazy {
+        fields.push(format!("lazy = {}", lazy));
+    }
+    if !plugin.event.is_empty() {
+        fields.push(format!("event = {}", strings(&plugin.event)));
+    }
+    if !plugin.ft.is_empty() {
+        fields.push(format!("ft = {}", strings(&plugin.ft)));
+    }
+    if !plugin.dependencies.is_empty() {
+        fields.push(format!("dependencies = {}", strings(&plugin.dependencies)));
+    }
+    if let Some(config) = &plugin.config {
+        fields.push(format!(
+            "config = function()\n{}\nend",
+            indent(config.trim_end(), 1)
+        ));
+    }
+    format!(
+        "{{\n{}\n}},",
+        fields
+            .iter()
+            .map(|field| indent(&format!("{},", field), 1))
+            .collect::<Vec<_>>()
+            .join("\n")
+    )
+}
+
+/// Defines `body` as a function taking `parameters` in the global lua table `table` using a
+/// `lua << EOF` heredoc,
+/// returns the definition and the expression referring to the function.
+fn lua_function(table: &str, key: &str, parameters: &str, body: &str) -> (String, String) {
+    let marker = heredoc_marker(body);
+    let function = format!("{}[{}]", table, key);
+    let definition = format!(
+        "lua << {marker}\n\
+         {table} = {table} or {{}}\n\
+         {function} = function({parameters})\n\
+         {body}\n\
+         end\n\
+         {marker}",
+        marker = marker,
+        table = table,
+        function = function,
+        parameters = parameters,
+        body = indent(body, 1)
+    );
+    (definition, function)
+}
+
+/// A `lua << EOF` end marker that does not occur as a line of `body`.
+fn heredoc_marker(body: &str) -> String {
+    let mut marker = "EOF".to_string();
+    while body.lines().any(|line| line.trim() == marker) {
+        marker.push('_');
+    }
+    marker
+}
+
+/// Escapes an autocommand pattern, where whitespace or a `|` would otherwise end it.
+fn vim_pattern(pattern: &str) -> String {
+    let mut escaped = String::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        if c == '|' || c.is_whitespace() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A string value of `:set`, escaping the characters that would otherwise end the value or start
+/// a comment (see `:help option-backslash`).
+fn set_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ' ' | '\t' | '\\' | '|' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A line generated by [`Backend::map`], split for [`Backend::align_mappings`].
+struct MapColumns<'a> {
+    indent: &'a str,
+    columns: Vec<&'a str>,
+    rest: &'a str,
+}
+
+/// Pads every column of `block` to the widest entry, dropping columns that are empty in every
+/// line.
+fn align_block(block: &[MapColumns]) -> Vec<String> {
+    let mut widths = Vec::new();
+    for line in block {
+        widths.resize(line.columns.len().max(widths.len()), 0);
+        for (width, column) in widths.iter_mut().zip(&line.columns) {
+            *width = column.chars().count().max(*width);
+        }
+    }
+    block
+        .iter()
+        .map(|line| {
+            let mut aligned = line.indent.to_string();
+            for (width, column) in widths.iter().zip(&line.columns) {
+                if *width > 0 {
+                    aligned.push_str(&format!("{:width$} ", column, width = width));
+                }
+            }
+            aligned.push_str(line.rest);
+            aligned
+        })
+        .collect()
+}
+
+/// The length of the string literal generated by [`lua_string`] at the start of `text`,
+/// including the quotes.
+fn lua_string_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next()?;
+            }
+            '"' => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A single-quoted vimscript string, where only `'` needs escaping (as `''`).
+pub(crate) fn vim_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Indents every non-empty line of `text` by `level` levels of two spaces.
+pub(crate) fn indent(text: &str, level: usize) -> String {
+    text.lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", "  ".repeat(level), line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn lua_string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    string.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str(r#"\""#),
+            '\\' => string.push_str(r"\\"),
+            '\n' => string.push_str(r"\n"),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
+
+/// Accesses the field `name`, using the `.name` shorthand where possible.
+fn lua_index(name: &str) -> String {
+    let is_identifier = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    if is_identifier && !name.is_empty() {
+        format!(".{}", name)
+    } else {
+        format!("[{}]", lua_string(name))
+    }
+}
+
+/// A key of a table constructor, using the `name = ` shorthand where possible.
+fn lua_key(name: &str) -> String {
+    match lua_index(name).strip_prefix('.') {
+        Some(name) => name.to_string(),
+        None => format!("[{}]", lua_string(name)),
+    }
+}
+
+fn lua_list(items: impl Iterator<Item = String>) -> String {
+    let items = items.collect::<Vec<_>>();
+    if items.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", items.join(", "))
+    }
+}
+
+fn lua_value(value: &Value) -> String {
+    match value {
+        Value::Int(value) => value.to_string(),
+        Value::Float(value) => format!("{:?}", value),
+        Value::String(value) => lua_string(value),
+        Value::Expr(Expression { expr }) => format!("vim.fn.eval({})", lua_string(expr)),
+        Value::Bool(value) => value.to_string(),
+        Value::List(values) => lua_list(values.iter().map(lua_value)),
+        Value::Map(entries) => lua_list(
+            entries
+                .iter()
+                .map(|(key, value)| format!("{} = {}", lua_key(key), lua_value(value))),
+        ),
+    }
+}
@@ -0,0 +1,256 @@
+use std::{env, ffi::OsString, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use nvim_config::Backend;
+
+const USAGE: &str = "\
+Usage: nvc [OPTIONS]
+
+Generates vimscript or lua from the YAML, TOML and JSON files in the config dir. The files are
+processed in lexical order of their paths, including the ones in subdirectories, so later files
+override earlier ones. Files starting with `_` that another file `extends` are only used
+through it.
+
+Options:
+    --config-dir <PATH>  Folder containing the config files [default: <nvim>/config]
+    --output-dir <PATH>  Folder to write plugin/ and ftplugin/ into [default: <nvim>]
+    --stdin <FORMAT>     Read a single config in FORMAT (`yaml`, `json` or `toml`) from stdin instead
+                         of the config dir
+    --dry-run            Print the generated files instead of writing them
+    --diff               Print a diff of the generated files against the ones on disk instead of
+                         writing them
+    --check              Fail if the generated files differ from the ones on disk
+    --backend <BACKEND>  Language to generate, `vim` or `lua`, config files can choose their own with
+                         `backend` [default: vim]
+    --strict             Treat conflicting mappings as errors instead of warnings
+    --allow-unknown-events
+                         Warn about unknown autocommand events instead of failing
+    --expand-env         Expand `${VAR}` in values, mapping rhs and autocommand commands
+    --split              Write keybindings, autocommands and options to plugin/keys, plugin/autocmds
+                         and plugin/options instead of plugin/config
+    --manifest           Write plugin/.nvim-config-manifest.json listing where every mapping,
+                         autocommand, option etc. came from and which file it was written to
+    --line-ending <LINE_ENDING>
+                         Line ending of the generated files, `lf` or `crlf` [default: lf]
+    --format             Align the lhs and rhs of consecutive mappings
+    --group-leader       Group leader mappings by their first key after the leader, with a comment
+                         heading each group
+    --annotate           Precede every mapping and abbreviation with a comment naming the config file
+                         and modes it came from
+    --error-format <ERROR_FORMAT>
+                         Print errors as `human` readable text or as a `json` array of {file, line,
+                         column, message} objects [default: human]
+    --profile            Print how long scanning, parsing (per file), emission and writing took
+    --watch              Regenerate whenever a config file changes, checked by polling every 500ms
+    --init               Create the config dir with a commented example config
+    --import <PATH>      Print a YAML config for the mappings, autocommands, options and variables of
+                         an existing vimscript config like plugin/config.vim, without generating
+    --validate           Check all config files and report every error, without generating
+    --list-filetypes     Print the file types the config files generate ftplugin files for, without
+                         generating
+    -q, --quiet          Only print warnings and errors, not the summary
+    -v, --verbose        Also print every file parsed and written
+    -h, --help           Print this help";
+
+/// How errors are printed on stderr.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// The error with its causes, like `Error: ...`.
+    Human,
+    /// A JSON array of the errors, see [`nvim_config::errors_to_json`].
+    Json,
+}
+
+/// The line ending of the generated files, config files are always read with either.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+pub struct Args {
+    pub config_dir: PathBuf,
+    pub output_dir: PathBuf,
+    /// The format of the config read from stdin, see `--stdin`.
+    pub stdin: Option<String>,
+    pub dry_run: bool,
+    pub diff: bool,
+    pub check: bool,
+    pub backend: Backend,
+    pub strict: bool,
+    pub allow_unknown_events: bool,
+    pub expand_env: bool,
+    pub split: bool,
+    pub manifest: bool,
+    pub format: bool,
+    pub line_ending: LineEnding,
+    pub group_leader: bool,
+    pub annotate: bool,
+    pub error_format: ErrorFormat,
+    pub profile: bool,
+    pub watch: bool,
+    pub validate: bool,
+    pub list_file_types: bool,
+    pub init: bool,
+    /// The vimscript file to convert into a config, see `--import`.
+    pub import: Option<PathBuf>,
+    pub quiet: bool,
+    pub verbose: bool,
+}
+
+impl Args {
+    pub fn parse() -> Result<Self> {
+        let mut config_dir = None;
+        let mut output_dir = None;
+        let mut stdin = None;
+        let mut dry_run = false;
+        let mut diff = false;
+        let mut check = false;
+        let mut backend = Backend::Vim;
+        let mut strict = false;
+        let mut allow_unknown_events = false;
+        let mut expand_env = false;
+        let mut split = false;
+        let mut manifest = false;
+        let mut format = false;
+        let mut line_ending = LineEnding::Lf;
+        let mut group_leader = false;
+        let mut annotate = false;
+        let mut error_format = ErrorFormat::Human;
+        let mut profile = false;
+        let mut watch = false;
+        let mut validate = false;
+        let mut list_file_types = false;
+        let mut init = false;
+        let mut import = None;
+        let mut quiet = false;
+        let mut verbose = false;
+
+        let mut args = env::args_os().skip(1);
+        while let Some(arg) = args.next() {
+            let arg = arg
+                .into_string()
+                .map_err(|arg| anyhow::anyhow!("Invalid argument: {:?}", arg))?;
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(OsString::from(value))),
+                None => (arg.as_str(), None),
+            };
+            let switch = || match value {
+                Some(_) => bail!("`{}` does not take a value", flag),
+                None => Ok(true),
+            };
+            let mut value = || {
+                value
+                    .clone()
+                    .or_else(|| args.next())
+                    .with_context(|| format!("Missing value for `{}`", flag))
+            };
+            match flag {
+                "--config-dir" => config_dir = Some(PathBuf::from(value()?)),
+                "--output-dir" => output_dir = Some(PathBuf::from(value()?)),
+                "--stdin" => {
+                    let format = value()?
+                        .into_string()
+                        .map_err(|_| anyhow::anyhow!("Invalid value for `{}`", flag))?
+                        .to_lowercase();
+                    if !["yaml", "yml", "json", "toml"].contains(&format.as_str()) {
+                        bail!(
+                            "Unsupported format for `{}`: `{}` (supported: yaml, json, toml)",
+                            flag,
+                            format
+                        );
+                    }
+                    stdin = Some(format);
+                }
+                "--dry-run" => dry_run = switch()?,
+                "--diff" => diff = switch()?,
+                "--check" => check = switch()?,
+                "--strict" => strict = switch()?,
+                "--allow-unknown-events" => allow_unknown_events = switch()?,
+                "--expand-env" => expand_env = switch()?,
+                "--split" => split = switch()?,
+                "--manifest" => manifest = switch()?,
+                "--format" => format = switch()?,
+                "--line-ending" => {
+                    line_ending = match value()?.to_str().map(str::to_ascii_lowercase).as_deref() {
+                        Some("lf") => LineEnding::Lf,
+                        Some("crlf") => LineEnding::Crlf,
+                        _ => bail!("Invalid value for `{}` (supported: lf, crlf)", flag),
+                    }
+                }
+                "--group-leader" => group_leader = switch()?,
+                "--annotate" => annotate = switch()?,
+                "--error-format" => {
+                    error_format = match value()?.to_str().map(str::to_ascii_lowercase).as_deref() {
+                        Some("human") => ErrorFormat::Human,
+                        Some("json") => ErrorFormat::Json,
+                        _ => bail!("Invalid value for `{}` (supported: human, json)", flag),
+                    }
+                }
+                "--profile" => profile = switch()?,
+                "--watch" => watch = switch()?,
+                "--validate" => validate = switch()?,
+                "--list-filetypes" => list_file_types = switch()?,
+                "--init" => init = switch()?,
+                "--import" => import = Some(PathBuf::from(value()?)),
+                "-q" | "--quiet" => quiet = switch()?,
+                "-v" | "--verbose" => verbose = switch()?,
+                "--backend" => {
+                    backend = value()?
+                        .to_str()
+                        .with_context(|| format!("Invalid value for `{}`", flag))?
+                        .parse()?
+                }
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                _ => bail!("Unknown argument: `{}`\n\n{}", arg, USAGE),
+            }
+        }
+
+        if quiet && verbose {
+            bail!("`--quiet` and `--verbose` are mutually exclusive");
+        }
+        if stdin.is_some() && (watch || validate || init || list_file_types) {
+            bail!(
+                "`--stdin` cannot be combined with `--watch`, `--validate`, `--list-filetypes` or \
+                 `--init`"
+            );
+        }
+
+        let nvim_dir = || {
+            dirs::config_dir()
+                .expect("There should be a config_dir")
+                .join("nvim")
+        };
+        Ok(Args {
+            config_dir: config_dir.unwrap_or_else(|| nvim_dir().join("config")),
+            output_dir: output_dir.unwrap_or_else(nvim_dir),
+            stdin,
+            dry_run,
+            diff,
+            check,
+            backend,
+            strict,
+            allow_unknown_events,
+            expand_env,
+            split,
+            manifest,
+            format,
+            line_ending,
+            group_leader,
+            annotate,
+            error_format,
+            profile,
+            watch,
+            validate,
+            list_file_types,
+            init,
+            import,
+            quiet,
+            verbose,
+        })
+    }
+}
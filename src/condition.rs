@@ -0,0 +1,173 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{bail, Context};
+use serde_with::DeserializeFromStr;
+
+/// A condition of `when`, e.g. `os == "macos"`, `nvim >= "0.10"` or `has("termguicolors")`.
+///
+/// `os` conditions are checked while generating, `nvim` and `has` conditions at runtime using
+/// `has()`.
+#[derive(DeserializeFromStr)]
+pub struct Condition {
+    kind: Kind,
+    /// The original text, used in comments.
+    text: String,
+}
+
+enum Kind {
+    Os {
+        os: String,
+        equal: bool,
+    },
+    Nvim {
+        operator: Operator,
+        version: [u32; 3],
+    },
+    /// A feature Neovim has to be compiled with (or without, if not `present`).
+    Has {
+        feature: String,
+        present: bool,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl Condition {
+    /// Whether the condition holds, `None` if it can only be checked at runtime.
+    pub fn evaluate(&self) -> Option<bool> {
+        match &self.kind {
+            Kind::Os { os, equal } => Some((std::env::consts::OS == os) == *equal),
+            Kind::Nvim { .. } | Kind::Has { .. } => None,
+        }
+    }
+
+    /// The runtime check as alternatives of `has()` features that all need to be present
+    /// (`true`) or absent (`false`).
+    pub fn features(&self) -> Vec<Vec<(String, bool)>> {
+        let (operator, version) = match &self.kind {
+            Kind::Nvim { operator, version } => (operator, version),
+            Kind::Has { feature, present } => return vec![vec![(feature.clone(), *present)]],
+            Kind::Os { .. } => return Vec::new(),
+        };
+        // `has("nvim-x.y.z")` checks for at least that version
+        let at_least = format!("nvim-{}.{}.{}", version[0], version[1], version[2]);
+        let above = format!("nvim-{}.{}.{}", version[0], version[1], version[2] + 1);
+        match operator {
+            Operator::GreaterEqual => vec![vec![(at_least, true)]],
+            Operator::Less => vec![vec![(at_least, false)]],
+            Operator::Greater => vec![vec![(above, true)]],
+            Operator::LessEqual => vec![vec![(above, false)]],
+            Operator::Equal => vec![vec![(at_least, true), (above, false)]],
+            Operator::NotEqual => vec![vec![(at_least, false)], vec![(above, true)]],
+        }
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromStr for Condition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let (present, negated) = match text.strip_prefix('!') {
+            Some(negated) => (false, negated.trim_start()),
+            None => (true, text),
+        };
+        if let Some(feature) = negated
+            .strip_prefix("has(")
+            .and_then(|feature| feature.strip_suffix(')'))
+        {
+            let feature = unquote(feature.trim());
+            if feature.is_empty() {
+                bail!("Missing feature in condition `{}`", text);
+            }
+            return Ok(Condition {
+                kind: Kind::Has {
+                    feature: feature.to_string(),
+                    present,
+                },
+                text: text.to_string(),
+            });
+        }
+        let subject_end = text
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(text.len());
+        let (subject, rest) = text.split_at(subject_end);
+        let rest = rest.trim_start();
+        let (operator, value) = [
+            (">=", Operator::GreaterEqual),
+            ("<=", Operator::LessEqual),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            (">", Operator::Greater),
+            ("<", Operator::Less),
+        ]
+        .iter()
+        .find_map(|(token, operator)| rest.strip_prefix(token).map(|value| (*operator, value)))
+        .with_context(|| {
+            format!(
+                "Missing operator in condition `{}` (supported: ==, !=, <, <=, >, >=)",
+                text
+            )
+        })?;
+        let value = unquote(value.trim());
+
+        let kind = match subject {
+            "os" => Kind::Os {
+                os: value.to_string(),
+                equal: match operator {
+                    Operator::Equal => true,
+                    Operator::NotEqual => false,
+                    _ => bail!("`os` only supports `==` and `!=`: `{}`", text),
+                },
+            },
+            "nvim" => {
+                let mut version = [0; 3];
+                let parts: Vec<_> = value.split('.').collect();
+                if parts.len() > 3 {
+                    bail!("Invalid version `{}` in condition `{}`", value, text);
+                }
+                for (part, component) in parts.into_iter().zip(&mut version) {
+                    *component = part.parse().with_context(|| {
+                        format!("Invalid version `{}` in condition `{}`", value, text)
+                    })?;
+                }
+                Kind::Nvim { operator, version }
+            }
+            _ => bail!(
+                "Unsupported condition `{}` (supported: `os`, `nvim`, `has()`)",
+                text
+            ),
+        };
+        Ok(Condition {
+            kind,
+            text: text.to_string(),
+        })
+    }
+}
+
+/// `value` without the single or double quotes around it, if any.
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| {
+            value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+        })
+        .unwrap_or(value)
+}
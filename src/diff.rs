@@ -0,0 +1,247 @@
+//! A minimal unified diff for `--diff`.
+
+/// Lines of unchanged context around every change.
+const CONTEXT: usize = 3;
+
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// The changes from `old` to `new` with the fewest removed and added lines. Leading and trailing
+/// lines both have in common are left out of the search in [`changes`].
+fn lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let mut lines: Vec<_> = old[..prefix].iter().map(|line| Line::Same(line)).collect();
+    lines.extend(changes(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    lines.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| Line::Same(line)),
+    );
+    lines
+}
+
+/// The shortest edit script from `old` to `new` by Myers' "An O(ND) Difference Algorithm": for
+/// `D` removed and added lines it takes O((N + M) * D) time and O(D²) space, instead of the
+/// O(N * M) of a table of common subsequences, so large generated files with few changes stay
+/// cheap.
+fn changes<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    // `trace[d][i]` is the furthest `x` (index into `old`) reached with `d` edits on the
+    // diagonal `k = x - y = 2 * i - d`
+    let mut trace: Vec<Vec<usize>> = Vec::new();
+    // Whether the furthest point on diagonal `i` of `d` edits comes from an added line, i.e.
+    // from the diagonal above in the previous round
+    let added = |previous: &[usize], d: usize, i: usize| {
+        i == 0 || (i != d && previous[i - 1] < previous[i])
+    };
+    'search: for d in 0..=old.len() + new.len() {
+        let mut round = Vec::with_capacity(d + 1);
+        for i in 0..=d {
+            let mut x = match trace.last() {
+                None => 0,
+                Some(previous) if added(previous, d, i) => previous[i],
+                Some(previous) => previous[i - 1] + 1,
+            };
+            let mut y = x + d - 2 * i;
+            while x < old.len() && y < new.len() && old[x] == new[y] {
+                x += 1;
+                y += 1;
+            }
+            round.push(x);
+            if x >= old.len() && y >= new.len() {
+                trace.push(round);
+                break 'search;
+            }
+        }
+        trace.push(round);
+    }
+
+    // Walk back from the end, collecting the lines in reverse
+    let mut lines = Vec::new();
+    let (mut x, mut y) = (old.len(), new.len());
+    for d in (1..trace.len()).rev() {
+        let previous = &trace[d - 1];
+        let i = (x + d - y) / 2;
+        let (start_x, start_y) = if added(previous, d, i) {
+            let start_x = previous[i];
+            (start_x, start_x + d - 2 * i)
+        } else {
+            let start_x = previous[i - 1] + 1;
+            (start_x, start_x + d - 2 * i)
+        };
+        while x > start_x && y > start_y {
+            x -= 1;
+            y -= 1;
+            lines.push(Line::Same(old[x]));
+        }
+        if added(previous, d, i) {
+            y -= 1;
+            lines.push(Line::Added(new[y]));
+        } else {
+            x -= 1;
+            lines.push(Line::Removed(old[x]));
+        }
+    }
+    while x > 0 {
+        x -= 1;
+        lines.push(Line::Same(old[x]));
+    }
+    lines.reverse();
+    lines
+}
+
+/// The unified diff from `old` (named `old_name`) to `new`, `None` if they are the same.
+pub fn unified(old_name: &str, new_name: &str, old: &str, new: &str) -> Option<String> {
+    if old == new {
+        return None;
+    }
+    let old: Vec<_> = old.lines().collect();
+    let new: Vec<_> = new.lines().collect();
+    let lines = lines(&old, &new);
+
+    // The line numbers in `old` and `new` before each line of the diff
+    let mut positions = Vec::with_capacity(lines.len() + 1);
+    let (mut old_line, mut new_line) = (0, 0);
+    for line in &lines {
+        positions.push((old_line, new_line));
+        match line {
+            Line::Same(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            Line::Removed(_) => old_line += 1,
+            Line::Added(_) => new_line += 1,
+        }
+    }
+    positions.push((old_line, new_line));
+
+    // Changes closer than twice the context share a hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (index, _) in lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Line::Same(_)))
+    {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + 1 + CONTEXT).min(lines.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    if hunks.is_empty() {
+        // Only the trailing newline differs
+        return None;
+    }
+
+    let mut diff = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (start, end) in hunks {
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        // Empty ranges are given by the line before them
+        let range = |start: usize, end: usize| {
+            let first = if end > start { start + 1 } else { start };
+            format!("{},{}", first, end - start)
+        };
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_end),
+            range(new_start, new_end)
+        ));
+        for line in &lines[start..end] {
+            let (prefix, text) = match line {
+                Line::Same(text) => (' ', text),
+                Line::Removed(text) => ('-', text),
+                Line::Added(text) => ('+', text),
+            };
+            diff.push(prefix);
+            diff.push_str(text);
+            diff.push('\n');
+        }
+    }
+    Some(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(old: &str, new: &str) -> Option<String> {
+        unified("old", "new", old, new)
+    }
+
+    #[test]
+    fn identical() {
+        assert_eq!(diff("a\nb\n", "a\nb\n"), None);
+        assert_eq!(diff("", ""), None);
+        // Only the trailing newline differs
+        assert_eq!(diff("a\nb", "a\nb\n"), None);
+    }
+
+    #[test]
+    fn insert() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nb\nx\nc\n").unwrap(),
+            "--- old\n+++ new\n@@ -1,3 +1,4 @@\n a\n b\n+x\n c\n"
+        );
+        assert_eq!(
+            diff("", "a\nb\n").unwrap(),
+            "--- old\n+++ new\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+    }
+
+    #[test]
+    fn delete() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nc\n").unwrap(),
+            "--- old\n+++ new\n@@ -1,3 +1,2 @@\n a\n-b\n c\n"
+        );
+        assert_eq!(
+            diff("a\nb\n", "").unwrap(),
+            "--- old\n+++ new\n@@ -1,2 +0,0 @@\n-a\n-b\n"
+        );
+    }
+
+    #[test]
+    fn change() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nx\nc\n").unwrap(),
+            "--- old\n+++ new\n@@ -1,3 +1,3 @@\n a\n-b\n+x\n c\n"
+        );
+        // Changes further apart than twice the context get their own hunks
+        let old: String = (1..=20).map(|line| format!("{}\n", line)).collect();
+        let new = old.replace("\n2\n", "\nx\n").replace("\n18\n", "\ny\n");
+        assert_eq!(
+            diff(&old, &new).unwrap(),
+            "--- old\n+++ new\n@@ -1,5 +1,5 @@\n 1\n-2\n+x\n 3\n 4\n 5\n@@ -15,6 +15,6 @@\n 15\n 16\n \
+             17\n-18\n+y\n 19\n 20\n"
+        );
+    }
+
+    #[test]
+    fn large_files() {
+        // Without a table of every pair of lines, this takes neither long nor much memory
+        let old: String = (0..200_000).map(|line| format!("{}\n", line)).collect();
+        let new = old.replace("\n100000\n", "\nchanged\n");
+        assert_eq!(
+            diff(&old, &new).unwrap(),
+            "--- old\n+++ new\n@@ -99998,7 +99998,7 @@\n 99997\n 99998\n 99999\n-100000\n+changed\n \
+             100001\n 100002\n 100003\n"
+        );
+    }
+}
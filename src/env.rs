@@ -0,0 +1,54 @@
+use std::env;
+
+use anyhow::{bail, Context, Result};
+
+use crate::Value;
+
+/// Replaces every `${VAR}` in `text` with the value of the environment variable `VAR`, `$${`
+/// escapes a literal `${`.
+pub fn expand(text: &str) -> Result<String> {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(escaped) = rest.strip_prefix("$${") {
+            expanded.push_str("${");
+            rest = escaped;
+        } else if let Some(variable) = rest.strip_prefix("${") {
+            let Some(end) = variable.find('}') else {
+                bail!("Unterminated `${{` in `{}`", text);
+            };
+            let name = &variable[..end];
+            let value = env::var(name)
+                .with_context(|| format!("Environment variable `{}` is not set", name))?;
+            expanded.push_str(&value);
+            rest = &variable[end + 1..];
+        } else {
+            expanded.push('$');
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Expands the strings in `value`, see [`expand`].
+pub fn expand_value(value: Value) -> Result<Value> {
+    Ok(match value {
+        Value::String(value) => Value::String(expand(&value)?),
+        Value::List(values) => Value::List(
+            values
+                .into_iter()
+                .map(expand_value)
+                .collect::<Result<_>>()?,
+        ),
+        Value::Map(entries) => Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| Ok((key, expand_value(value)?)))
+                .collect::<Result<_>>()?,
+        ),
+        value => value,
+    })
+}
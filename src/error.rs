@@ -0,0 +1,105 @@
+//! The kinds of failures, see [`Error`].
+
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use crate::manifest::json_string;
+
+/// The kind of a failure, attached to the [`anyhow::Error`]s returned by this crate. Match on it
+/// with `error.downcast_ref::<nvim_config::Error>()`, the message stays the same as without it.
+#[derive(Debug)]
+pub enum Error {
+    /// A config file is not valid YAML, TOML or JSON, or does not have the expected structure.
+    Parse {
+        file: String,
+        message: String,
+        /// The 1-based line and column, if the parser reported them.
+        location: Option<(usize, usize)>,
+    },
+    /// Unsupported or conflicting `flag`s of a mapping or abbreviation.
+    Flag {
+        file: String,
+        flag: String,
+        message: String,
+        /// The 1-based line and column of the flags, if they are written in the file itself.
+        location: Option<(usize, usize)>,
+    },
+    /// A config file that parsed, but is inconsistent, like a user command with both `rhs` and
+    /// `lua`.
+    Validation { file: String, message: String },
+    /// Reading `path` failed, the [`std::io::Error`] follows in the error chain.
+    Io { path: PathBuf, message: String },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse { message, .. }
+            | Error::Flag { message, .. }
+            | Error::Validation { message, .. }
+            | Error::Io { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// `errors` as a JSON array of `{file, line, column, message}` objects, e.g. for a quickfix list.
+/// `file` is the path of the config file in `config_dir` (`null` if the error is not about one),
+/// `line` and `column` are 1-based and only known for errors of the YAML, TOML or JSON parser.
+pub fn to_json(errors: &[anyhow::Error], config_dir: &Path) -> String {
+    let errors: Vec<_> = errors
+        .iter()
+        .map(|error| {
+            let kind = error.downcast_ref::<Error>();
+            let (file, location) = match kind {
+                Some(Error::Parse { file, location, .. } | Error::Flag { file, location, .. }) => {
+                    (Some(config_dir.join(document_file(file))), *location)
+                }
+                Some(Error::Validation { file, .. }) => {
+                    (Some(config_dir.join(document_file(file))), None)
+                }
+                Some(Error::Io { path, .. }) => (Some(path.clone()), None),
+                None => (None, None),
+            };
+            let mut causes: Vec<_> = error.chain().map(ToString::to_string).collect();
+            // That message repeats the location with a snippet, the causes after it say what
+            // is wrong
+            if let (Some(kind), Some(_)) = (kind, location) {
+                let kind = kind.to_string();
+                if let Some(index) = causes.iter().position(|cause| *cause == kind) {
+                    if index + 1 < causes.len() {
+                        causes.drain(..=index);
+                    }
+                }
+            }
+            let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+            format!(
+                "  {{\"file\": {}, \"line\": {}, \"column\": {}, \"message\": {}}}",
+                optional(file.map(|file| json_string(&file.display().to_string()))),
+                optional(location.map(|(line, _)| line.to_string())),
+                optional(location.map(|(_, column)| column.to_string())),
+                json_string(&causes.join(": "))
+            )
+        })
+        .collect();
+    if errors.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", errors.join(",\n"))
+    }
+}
+
+/// The file of the config `name`, without the `#2` of the documents of a YAML file.
+fn document_file(name: &str) -> &str {
+    match name.rsplit_once('#') {
+        Some((file, document))
+            if !document.is_empty() && document.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            file
+        }
+        _ => name,
+    }
+}
@@ -0,0 +1,146 @@
+/// Autocommand events known to Neovim, see `:help autocmd-events`.
+pub const EVENTS: &[&str] = &[
+    "BufAdd",
+    "BufCreate",
+    "BufDelete",
+    "BufEnter",
+    "BufFilePost",
+    "BufFilePre",
+    "BufHidden",
+    "BufLeave",
+    "BufModifiedSet",
+    "BufNew",
+    "BufNewFile",
+    "BufRead",
+    "BufReadCmd",
+    "BufReadPost",
+    "BufReadPre",
+    "BufUnload",
+    "BufWinEnter",
+    "BufWinLeave",
+    "BufWipeout",
+    "BufWrite",
+    "BufWriteCmd",
+    "BufWritePost",
+    "BufWritePre",
+    "ChanInfo",
+    "ChanOpen",
+    "CmdUndefined",
+    "CmdlineChanged",
+    "CmdlineEnter",
+    "CmdlineLeave",
+    "CmdwinEnter",
+    "CmdwinLeave",
+    "ColorScheme",
+    "ColorSchemePre",
+    "CompleteChanged",
+    "CompleteDone",
+    "CompleteDonePre",
+    "CursorHold",
+    "CursorHoldI",
+    "CursorMoved",
+    "CursorMovedC",
+    "CursorMovedI",
+    "DiagnosticChanged",
+    "DiffUpdated",
+    "DirChanged",
+    "DirChangedPre",
+    "EncodingChanged",
+    "ExitPre",
+    "FileAppendCmd",
+    "FileAppendPost",
+    "FileAppendPre",
+    "FileChangedRO",
+    "FileChangedShell",
+    "FileChangedShellPost",
+    "FileEncoding",
+    "FileReadCmd",
+    "FileReadPost",
+    "FileReadPre",
+    "FileType",
+    "FileWriteCmd",
+    "FileWritePost",
+    "FileWritePre",
+    "FilterReadPost",
+    "FilterReadPre",
+    "FilterWritePost",
+    "FilterWritePre",
+    "FocusGained",
+    "FocusLost",
+    "FuncUndefined",
+    "InsertChange",
+    "InsertCharPre",
+    "InsertEnter",
+    "InsertLeave",
+    "InsertLeavePre",
+    "LspAttach",
+    "LspDetach",
+    "LspNotify",
+    "LspProgress",
+    "LspRequest",
+    "LspTokenUpdate",
+    "MenuPopup",
+    "ModeChanged",
+    "OptionSet",
+    "QuickFixCmdPost",
+    "QuickFixCmdPre",
+    "QuitPre",
+    "RecordingEnter",
+    "RecordingLeave",
+    "RemoteReply",
+    "SafeState",
+    "SearchWrapped",
+    "SessionLoadPost",
+    "SessionWritePost",
+    "ShellCmdPost",
+    "ShellFilterPost",
+    "Signal",
+    "SourceCmd",
+    "SourcePost",
+    "SourcePre",
+    "SpellFileMissing",
+    "StdinReadPost",
+    "StdinReadPre",
+    "SwapExists",
+    "Syntax",
+    "TabClosed",
+    "TabEnter",
+    "TabLeave",
+    "TabNew",
+    "TabNewEntered",
+    "TermClose",
+    "TermEnter",
+    "TermLeave",
+    "TermOpen",
+    "TermRequest",
+    "TermResponse",
+    "TextChanged",
+    "TextChangedI",
+    "TextChangedP",
+    "TextChangedT",
+    "TextYankPost",
+    "UIEnter",
+    "UILeave",
+    "User",
+    "VimEnter",
+    "VimLeave",
+    "VimLeavePre",
+    "VimResized",
+    "VimResume",
+    "VimSuspend",
+    "WinClosed",
+    "WinEnter",
+    "WinLeave",
+    "WinNew",
+    "WinResized",
+    "WinScrolled",
+];
+
+/// Whether `trigger` is a known event, `*` matches every event. Like in Neovim the
+/// comparison ignores case.
+pub fn is_known(trigger: &str) -> bool {
+    trigger == "*"
+        || EVENTS
+            .iter()
+            .any(|event| event.eq_ignore_ascii_case(trigger))
+}
@@ -0,0 +1,199 @@
+//! `extends`: config files building on shared fragments.
+//!
+//! The files listed in `extends` (relative to the extending file, in order) are merged before the
+//! extending file itself, so later files win: maps like `keys` or `set_value` are merged key by
+//! key, the lists of [`LIST_SECTIONS`] are appended and any other value is replaced. Files
+//! starting with an `_` that another file in the config dir extends are skipped by
+//! [`crate::parse_dir`], so fragments like `_base.yaml` are only used where they are extended. Paths in `source` stay relative to the extending file.
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use serde_yaml::{Mapping, Value};
+
+use crate::{deserialize, format_of, unix_line_endings, Error};
+
+/// The key listing the files a config extends.
+const EXTENDS: &str = "extends";
+
+/// The sections that are lists (or a single entry), which are appended instead of replaced.
+const LIST_SECTIONS: &[&str] = &[
+    "when",
+    "auto_commands",
+    "augroups",
+    "set",
+    "set_default",
+    "unlet",
+    "plugins",
+    "source",
+];
+
+/// The config at `path` with its `source` text merged onto the files it extends, `None` if it
+/// does not extend any (or fails to parse, which is reported by the regular parser).
+pub(crate) fn resolve(path: &Path, source: &str, filename: &str) -> Result<Option<Value>> {
+    let Ok(Value::Mapping(mut config)) = parse(path, source, filename) else {
+        return Ok(None);
+    };
+    let Some(extends) = config.remove(&Value::from(EXTENDS)) else {
+        return Ok(None);
+    };
+    let mut stack = vec![(path.canonicalize()?, filename.to_string())];
+    let mut merged = Mapping::new();
+    for base in bases(extends, filename)? {
+        merge(&mut merged, load(path, &base, &mut stack)?);
+    }
+    merge(&mut merged, config);
+    Ok(Some(Value::Mapping(merged)))
+}
+
+/// The canonical paths of the files extended by any of `files`, files that fail to read or parse
+/// extend none (which is reported by the regular parser).
+pub(crate) fn extended(files: &[(PathBuf, String)]) -> HashSet<PathBuf> {
+    let mut extended = HashSet::new();
+    for (path, filename) in files {
+        let Ok(source) = fs::read_to_string(path) else {
+            continue;
+        };
+        let Ok(Value::Mapping(mut config)) = parse(path, &source, filename) else {
+            continue;
+        };
+        let Some(Ok(bases)) = config
+            .remove(&Value::from(EXTENDS))
+            .map(|extends| bases(extends, filename))
+        else {
+            continue;
+        };
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        extended.extend(
+            bases
+                .iter()
+                .filter_map(|base| dir.join(base).canonicalize().ok()),
+        );
+    }
+    extended
+}
+
+/// Loads the file `base` extended by the file at `path`, with the files it extends merged in.
+/// `stack` holds the files currently being loaded, to detect cycles.
+fn load(path: &Path, base: &str, stack: &mut Vec<(PathBuf, String)>) -> Result<Mapping> {
+    let base_path = path.parent().unwrap_or_else(|| Path::new("")).join(base);
+    let read_error = || Error::Io {
+        path: base_path.clone(),
+        message: format!(
+            "Failed to read extended file: {} in {}",
+            base_path.display(),
+            stack[stack.len() - 1].1
+        ),
+    };
+    let canonical = base_path.canonicalize().with_context(read_error)?;
+    if stack.iter().any(|(path, _)| path == &canonical) {
+        let cycle: Vec<_> = stack
+            .iter()
+            .map(|(_, name)| name.as_str())
+            .chain([base])
+            .collect();
+        bail!(Error::Parse {
+            file: stack[0].1.clone(),
+            message: format!("Cycle in `extends`: {}", cycle.join(" -> ")),
+            location: None,
+        });
+    }
+    let source = fs::read_to_string(&base_path)
+        .map(unix_line_endings)
+        .with_context(read_error)?;
+    let mut config = match parse(&base_path, &source, base)? {
+        Value::Mapping(config) => config,
+        Value::Null => Mapping::new(),
+        _ => bail!(Error::Parse {
+            file: base.to_string(),
+            message: format!("Extended file {} is not a map of sections", base),
+            location: None,
+        }),
+    };
+    stack.push((canonical, base.to_string()));
+    let mut merged = Mapping::new();
+    if let Some(extends) = config.remove(&Value::from(EXTENDS)) {
+        for next in bases(extends, base)? {
+            merge(&mut merged, load(&base_path, &next, stack)?);
+        }
+    }
+    stack.pop();
+    merge(&mut merged, config);
+    Ok(merged)
+}
+
+/// The files of an `extends` entry, either a single file or a list of them.
+fn bases(extends: Value, filename: &str) -> Result<Vec<String>> {
+    let bases = match extends {
+        Value::String(base) => return Ok(vec![base]),
+        Value::Sequence(bases) => bases,
+        _ => Vec::new(),
+    };
+    bases
+        .into_iter()
+        .map(|base| match base {
+            Value::String(base) => Ok(base),
+            _ => bail!(Error::Parse {
+                file: filename.to_string(),
+                message: format!(
+                    "`extends` in {} has to be a file or a list of files",
+                    filename
+                ),
+                location: None,
+            }),
+        })
+        .collect()
+}
+
+/// Parses YAML, JSON or TOML into a generic value, by the extension of `path`.
+fn parse(path: &Path, source: &str, filename: &str) -> Result<Value> {
+    deserialize(format_of(path).as_deref(), source, filename)
+}
+
+/// Merges the sections of `child` into `base`, see the module docs.
+fn merge(base: &mut Mapping, child: Mapping) {
+    for (key, value) in child {
+        let list = key.as_str().is_some_and(|key| LIST_SECTIONS.contains(&key));
+        match base.get_mut(&key) {
+            Some(existing) if list => {
+                let mut entries = into_list(existing.clone());
+                entries.extend(into_list(value));
+                *existing = Value::Sequence(entries);
+            }
+            Some(existing) => merge_value(existing, value),
+            None => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Merges maps key by key, anything else in `child` replaces `base`.
+fn merge_value(base: &mut Value, child: Value) {
+    match (base, child) {
+        (Value::Mapping(base), Value::Mapping(child)) => {
+            for (key, value) in child {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, child) => *base = child,
+    }
+}
+
+/// The entries of a section that is either a list or a single entry.
+fn into_list(value: Value) -> Vec<Value> {
+    match value {
+        Value::Sequence(entries) => entries,
+        Value::Null => Vec::new(),
+        entry => vec![entry],
+    }
+}
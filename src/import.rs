@@ -0,0 +1,553 @@
+//! `--import`: a YAML config from an existing vimscript config, the inverse of what the vim
+//! backend emits. Only the common forms of mappings, autocommands, options and variables are
+//! understood, every other line is reported as skipped.
+
+use indexmap::IndexMap;
+use serde_yaml::{Mapping, Value};
+
+use crate::events;
+
+/// The result of [`import_vim`].
+pub struct Import {
+    /// The config as YAML.
+    pub config: String,
+    /// The lines that were not understood with their (1-based) line number, to port by hand.
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// Converts the vimscript `source`, e.g. a `plugin/config.vim`, into a config. Later lines
+/// override earlier ones like they would in vim, comments and `augroup`s are dropped.
+pub fn import_vim(source: &str) -> Import {
+    let mut importer = Importer::default();
+    let mut skipped = Vec::new();
+    // The line ending a block that is skipped as a whole, like a function
+    let mut block_end: Option<String> = None;
+    for (number, line) in logical_lines(source) {
+        let line = trim(&line);
+        if let Some(end) = &block_end {
+            if line == end || (end == "endfunction" && line.starts_with("endf")) {
+                block_end = None;
+            }
+            continue;
+        }
+        if line.is_empty() || line.starts_with('"') {
+            continue;
+        }
+        let line = line.trim_start_matches(':');
+        let name_end = line
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(line.len());
+        let (cmd, rest) = line.split_at(name_end);
+        let (bang, rest) = match rest.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        let rest = rest.trim_start();
+        let imported = match cmd {
+            // Groups are generated per config file
+            "augroup" | "aug" => Some(()),
+            "autocmd" | "au" if bang && rest.is_empty() => Some(()),
+            "autocmd" | "au" if !bang => importer.autocmd(rest),
+            "set" | "se" if !bang => importer.set(rest),
+            "let" if !bang => importer.r#let(rest),
+            "function" | "fu" | "fun" => {
+                block_end = Some("endfunction".to_string());
+                None
+            }
+            "lua" if rest.starts_with("<<") => {
+                block_end = rest.split_whitespace().last().map(str::to_string);
+                None
+            }
+            _ => importer.map(cmd, bang, rest),
+        };
+        if imported.is_none() {
+            skipped.push((number, line.to_string()));
+        }
+    }
+    Import {
+        config: importer.config(),
+        skipped,
+    }
+}
+
+/// `line` without surrounding whitespace, except for an escaped space at the end like in
+/// `set showbreak=>\ `.
+fn trim(line: &str) -> &str {
+    let line = line.trim_start();
+    let trimmed = line.trim_end();
+    match line[trimmed.len()..].chars().next() {
+        Some(c) if trimmed.ends_with('\\') => &line[..trimmed.len() + c.len_utf8()],
+        _ => trimmed,
+    }
+}
+
+/// The lines of `source` with their line number, joining continuation lines starting with `\`.
+fn logical_lines(source: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        match (line.trim_start().strip_prefix('\\'), lines.last_mut()) {
+            (Some(continuation), Some((_, last))) => last.push_str(continuation),
+            _ => lines.push((index + 1, line.to_string())),
+        }
+    }
+    lines
+}
+
+/// The sections of the config, filled line by line.
+#[derive(Default)]
+struct Importer {
+    leader: Option<String>,
+    localleader: Option<String>,
+    /// The last mapping of every mode, `<buffer>`, leader flag and lhs, with the flags besides
+    /// the mode and the rhs.
+    keys: IndexMap<(char, bool, String, String), (String, Value)>,
+    auto_commands: Vec<Value>,
+    set: Vec<String>,
+    set_value: IndexMap<String, Value>,
+    set_default: Vec<String>,
+    r#let: IndexMap<String, Value>,
+}
+
+impl Importer {
+    /// `nnoremap <silent> lhs rhs` and the other `map` commands.
+    fn map(&mut self, cmd: &str, bang: bool, rest: &str) -> Option<()> {
+        let (modes, recursive) = match cmd.strip_suffix("noremap") {
+            Some(modes) => (modes, false),
+            None => (cmd.strip_suffix("map")?, true),
+        };
+        let modes = match (modes, bang) {
+            ("", false) => "nvo",
+            ("", true) => "im",
+            (mode, false) => match mode {
+                "n" | "v" | "s" | "o" | "i" | "t" => mode,
+                "c" => "m",
+                // `x` (visual without select) and `l` have no flag
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let mut flags = String::new();
+
+        let mut rest = rest;
+        let mut arguments = Vec::new();
+        'arguments: loop {
+            for argument in ["buffer", "nowait", "silent", "script", "expr", "unique"] {
+                if let Some(remainder) = strip_prefix_ignore_case(rest, &format!("<{}>", argument))
+                {
+                    arguments.push(argument);
+                    rest = remainder.trim_start();
+                    continue 'arguments;
+                }
+            }
+            break;
+        }
+        let (lhs, rhs) = rest.split_once(char::is_whitespace)?;
+        let rhs = rhs.trim_start().replace(r"\|", "|");
+        // `\<` means `<lt>` in the keys of a config
+        if rhs.is_empty() || lhs.contains(r"\<") {
+            return None;
+        }
+
+        let mut lhs = lhs;
+        if let Some(key) = strip_prefix_ignore_case(lhs, "<leader>").filter(|key| !key.is_empty()) {
+            flags.push('l');
+            lhs = key;
+        } else if let Some(key) =
+            strip_prefix_ignore_case(lhs, "<localleader>").filter(|key| !key.is_empty())
+        {
+            flags.push('k');
+            lhs = key;
+        }
+        // `<leader>f` and `f` are different mappings, `<leader>` and `<Leader>` the same
+        let mapped = (
+            arguments.contains(&"buffer"),
+            flags.clone(),
+            lhs.to_string(),
+        );
+        let expr = arguments.contains(&"expr");
+        let cmd = strip_prefix_ignore_case(&rhs, "<cmd>")
+            .and_then(|cmd| strip_suffix_ignore_case(cmd, "<cr>"))
+            .filter(|_| !expr);
+        let mut value = if rhs.eq_ignore_ascii_case("<nop>") {
+            flags.push('x');
+            Value::Null
+        } else if let Some(cmd) = cmd {
+            match cmd
+                .strip_prefix("lua (")
+                .and_then(|function| function.strip_suffix(")()"))
+            {
+                Some(function) => Value::from(format!("lua: {}", function)),
+                None => {
+                    flags.push('w');
+                    Value::from(cmd)
+                }
+            }
+        } else {
+            Value::from(rhs.as_str())
+        };
+        if recursive {
+            flags.push('r');
+        }
+        if expr {
+            flags.push('e');
+        }
+        // Mappings are silent by default
+        if !arguments.contains(&"silent") {
+            flags.push('u');
+        }
+        if arguments.contains(&"buffer") {
+            flags.push('b');
+        }
+        if arguments.contains(&"nowait") {
+            flags.push('a');
+        }
+        // `<Plug>` mappings are only silent with the `silent` option
+        let plug = strip_prefix_ignore_case(&rhs, "<plug>").is_some();
+        let opts: Vec<Value> = arguments
+            .iter()
+            .filter(|argument| {
+                ["script", "unique"].contains(argument) || (plug && **argument == "silent")
+            })
+            .map(|argument| Value::from(*argument))
+            .collect();
+        if !opts.is_empty() {
+            value = mapping(vec![("rhs", value), ("opts", Value::Sequence(opts))]);
+        }
+        for mode in modes.chars() {
+            self.keys.insert(
+                (mode, mapped.0, mapped.1.clone(), mapped.2.clone()),
+                (flags.clone(), value.clone()),
+            );
+        }
+        Some(())
+    }
+
+    /// `autocmd [group] events pattern [++once] [++nested] cmd`.
+    fn autocmd(&mut self, rest: &str) -> Option<()> {
+        let is_events = |token: &str| token.split(',').all(events::is_known);
+        let (mut triggers, mut rest) = next_token(rest);
+        if !is_events(triggers) {
+            // The augroup
+            let (token, remainder) = next_token(rest);
+            triggers = token;
+            rest = remainder;
+        }
+        if triggers.is_empty() || !is_events(triggers) {
+            return None;
+        }
+        let (pattern, mut rest) = next_token(rest);
+        let mut entries = vec![(
+            "triggers",
+            match triggers.split(',').collect::<Vec<_>>().as_slice() {
+                [trigger] => Value::from(*trigger),
+                triggers => Value::Sequence(triggers.iter().map(|t| Value::from(*t)).collect()),
+            },
+        )];
+        let pattern = unescape(pattern, |_| true);
+        if pattern != "*" {
+            entries.push(("matching", Value::from(pattern)));
+        }
+        let mut options = Vec::new();
+        loop {
+            let (token, remainder) = next_token(rest);
+            match token {
+                "++once" => options.push("once"),
+                "++nested" | "nested" => options.push("nested"),
+                "silent!" => options.push("silent"),
+                _ => break,
+            }
+            rest = remainder;
+        }
+        if rest.is_empty() {
+            return None;
+        }
+        match rest.strip_prefix("lua ") {
+            Some(lua) => entries.push(("lua", Value::from(lua.trim_start()))),
+            None => entries.push(("cmd", Value::from(rest))),
+        }
+        entries.extend(
+            options
+                .into_iter()
+                .map(|option| (option, Value::from(true))),
+        );
+        self.auto_commands.push(mapping(entries));
+        Some(())
+    }
+
+    /// `set number nowrap tabstop=4`, the whole line is skipped if any option is not understood.
+    fn set(&mut self, rest: &str) -> Option<()> {
+        let mut options = Vec::new();
+        let mut rest = rest;
+        while !rest.is_empty() && !rest.starts_with('"') {
+            let (option, remainder) = next_token(rest);
+            options.push(SetOption::parse(option)?);
+            rest = remainder;
+        }
+        for option in options {
+            match option {
+                SetOption::On(name) if self.set_value.contains_key(&name) => {
+                    self.set_value.insert(name, Value::from(true));
+                }
+                SetOption::On(name) => {
+                    if !self.set.contains(&name) {
+                        self.set.push(name);
+                    }
+                }
+                SetOption::Value(name, value) => {
+                    // `set` and `set_value` cannot both contain an option
+                    self.set.retain(|option| *option != name);
+                    self.set_value.insert(name, value);
+                }
+                SetOption::Default(name) => self.set_default.push(name),
+            }
+        }
+        Some(())
+    }
+
+    /// `let name = value`, assigning options with `let &name` goes to `set_value`.
+    fn r#let(&mut self, rest: &str) -> Option<()> {
+        let (name, value) = rest.split_once('=')?;
+        let name = name.trim();
+        let value = value.trim();
+        // `let x += 1` and friends
+        if name.ends_with(['+', '-', '*', '/', '%', '.']) || value.is_empty() {
+            return None;
+        }
+        if let Some(option) = name.strip_prefix('&') {
+            let option = option.strip_prefix("g:").unwrap_or(option);
+            if option.is_empty() || !option.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return None;
+            }
+            let value = match string_literal(value) {
+                Some(value) => Value::from(value),
+                None => match value.parse::<i64>() {
+                    Ok(value) => Value::from(value),
+                    Err(_) => expression(value),
+                },
+            };
+            self.set.retain(|name| name != option);
+            self.set_value.insert(option.to_string(), value);
+            return Some(());
+        }
+        let valid = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | ':' | '#');
+        if name.is_empty() || !name.chars().all(valid) {
+            return None;
+        }
+        let leader = match name.strip_prefix("g:").unwrap_or(name) {
+            "mapleader" => Some(&mut self.leader),
+            "maplocalleader" => Some(&mut self.localleader),
+            _ => None,
+        };
+        if let (Some(leader), Some(value)) = (leader, string_literal(value)) {
+            *leader = Some(value);
+            return Some(());
+        }
+        // Strings of `let` are taken literally, escapes need an expression
+        let value = if let Some(string) = string_literal(value) {
+            Value::from(string)
+        } else if let Ok(value) = value.parse::<i64>() {
+            Value::from(value)
+        } else if let Some(float) = value.parse::<f64>().ok().filter(|_| {
+            value.contains('.') && value.chars().all(|c| c.is_ascii_digit() || c == '.')
+        }) {
+            Value::from(float)
+        } else {
+            expression(value)
+        };
+        self.r#let.insert(name.to_string(), value);
+        Some(())
+    }
+
+    fn config(self) -> String {
+        let mut config = Mapping::new();
+        let mut insert = |key: &str, value: Value| {
+            config.insert(Value::from(key), value);
+        };
+        if let Some(leader) = self.leader {
+            insert("leader", Value::from(leader));
+        }
+        if let Some(localleader) = self.localleader {
+            insert("localleader", Value::from(localleader));
+        }
+        if !self.keys.is_empty() {
+            // The same mapping in several modes becomes one entry
+            let mut modes: IndexMap<(String, String, Value), String> = IndexMap::new();
+            for ((mode, _, _, lhs), (flags, value)) in self.keys {
+                modes.entry((flags, lhs, value)).or_default().push(mode);
+            }
+            let mut keys: IndexMap<String, Mapping> = IndexMap::new();
+            for ((flags, lhs, value), modes) in modes {
+                keys.entry(modes + &flags)
+                    .or_default()
+                    .insert(Value::from(lhs), value);
+            }
+            insert(
+                "keys",
+                Value::Mapping(
+                    keys.into_iter()
+                        .map(|(flags, keys)| (Value::from(flags), Value::Mapping(keys)))
+                        .collect(),
+                ),
+            );
+        }
+        if !self.auto_commands.is_empty() {
+            insert("auto_commands", Value::Sequence(self.auto_commands));
+        }
+        if !self.set_default.is_empty() {
+            insert("set_default", strings(self.set_default));
+        }
+        if !self.set.is_empty() {
+            insert("set", strings(self.set));
+        }
+        for (key, entries) in [("set_value", self.set_value), ("let", self.r#let)] {
+            if !entries.is_empty() {
+                insert(
+                    key,
+                    Value::Mapping(
+                        entries
+                            .into_iter()
+                            .map(|(name, value)| (Value::from(name), value))
+                            .collect(),
+                    ),
+                );
+            }
+        }
+        if config.is_empty() {
+            return "{}\n".to_string();
+        }
+        let yaml = serde_yaml::to_string(&config).expect("A YAML value can be serialized");
+        let yaml = yaml.strip_prefix("---\n").unwrap_or(&yaml);
+        format!("{}\n", yaml.trim_end())
+    }
+}
+
+/// An argument of `set`.
+enum SetOption {
+    /// `set number`
+    On(String),
+    /// `set tabstop=4` or `set nowrap`
+    Value(String, Value),
+    /// `set number&`
+    Default(String),
+}
+
+impl SetOption {
+    fn parse(option: &str) -> Option<Self> {
+        let name_end = option
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(option.len());
+        let (name, rest) = option.split_at(name_end);
+        if name.is_empty() || name.starts_with("inv") {
+            return None;
+        }
+        Some(match rest {
+            "" => match name.strip_prefix("no") {
+                Some(name) => SetOption::Value(name.to_string(), Value::from(false)),
+                None => SetOption::On(name.to_string()),
+            },
+            "&" => SetOption::Default(name.to_string()),
+            _ => {
+                let value = rest.strip_prefix(['=', ':'])?;
+                // See `:help option-backslash`
+                let value = unescape(value, |c| matches!(c, ' ' | '\t' | '\\' | '|' | '"'));
+                let value = match value.parse::<i64>() {
+                    Ok(value) => Value::from(value),
+                    Err(_) => Value::from(value),
+                };
+                SetOption::Value(name.to_string(), value)
+            }
+        })
+    }
+}
+
+/// The next argument of `s`, ending at the first whitespace that is not escaped by a backslash,
+/// and the rest after it.
+fn next_token(s: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (index, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c.is_whitespace() {
+            return (&s[..index], s[index..].trim_start());
+        }
+    }
+    (s, "")
+}
+
+/// Removes the backslashes before the characters that `escaped` returns true for.
+fn unescape(s: &str, escaped: impl Fn(char) -> bool) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(&next) if c == '\\' && escaped(next) => {
+                unescaped.push(next);
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// The contents of `value` if it is a single string literal quoted with `quote`, without
+/// unescaping them.
+fn quoted(value: &str, quote: char) -> Option<&str> {
+    let inner = value.strip_prefix(quote)?;
+    let mut chars = inner.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            // `''` is a quote in a single quoted string
+            c if c == quote && inner[index + 1..].starts_with(quote) && quote == '\'' => {
+                chars.next();
+            }
+            c if c == quote => return (index + 1 == inner.len()).then(|| &inner[..index]),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The value of the string literal `value`, for the double quoted ones only without escapes
+/// besides `\<Space>`, like in `let mapleader = "\<Space>"`.
+fn string_literal(value: &str) -> Option<String> {
+    if let Some(string) = quoted(value, '\'') {
+        return Some(string.replace("''", "'"));
+    }
+    let string = quoted(value, '"')?.replace(r"\<Space>", " ");
+    (!string.contains('\\')).then_some(string)
+}
+
+fn expression(expr: &str) -> Value {
+    mapping(vec![("expr", Value::from(expr))])
+}
+
+fn mapping(entries: Vec<(&str, Value)>) -> Value {
+    Value::Mapping(
+        entries
+            .into_iter()
+            .map(|(key, value)| (Value::from(key), value))
+            .collect(),
+    )
+}
+
+fn strings(strings: Vec<String>) -> Value {
+    Value::Sequence(strings.into_iter().map(Value::from).collect())
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    (s.len() >= prefix.len()
+        && s.is_char_boundary(prefix.len())
+        && s[..prefix.len()].eq_ignore_ascii_case(prefix))
+    .then(|| &s[prefix.len()..])
+}
+
+fn strip_suffix_ignore_case<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = s.len().checked_sub(suffix.len())?;
+    (s.is_char_boundary(start) && s[start..].eq_ignore_ascii_case(suffix)).then(|| &s[..start])
+}
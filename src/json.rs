@@ -0,0 +1,325 @@
+//! The parser of `.json` config files. JSON is not quite a subset of the YAML the YAML parser
+//! reads, e.g. the escape `\/` or tabs between tokens are only valid in JSON, so JSON files get
+//! their own parser. The document becomes a [`Value`], which configs are deserialized from like
+//! from YAML.
+
+use std::fmt::{self, Display};
+
+use serde_yaml::{Mapping, Number, Value};
+
+use crate::lines::{line_at, line_starts, Lines};
+
+/// A syntax error, at a 1-based line and column.
+#[derive(Debug)]
+pub(crate) struct Error {
+    message: String,
+    line: usize,
+    column: usize,
+}
+
+impl Error {
+    pub(crate) fn location(&self) -> (usize, usize) {
+        (self.line, self.column)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses the JSON document `source`. Like in most JSON parsers, the last of duplicate keys wins.
+pub(crate) fn parse(source: &str) -> Result<Value, Error> {
+    Parser::new(source).document()
+}
+
+/// The lines of the keys and items of the JSON document `source`, none if it does not parse.
+pub(crate) fn lines(source: &str) -> Lines {
+    let mut parser = Parser::new(source);
+    match parser.document() {
+        Ok(_) => parser.lines,
+        Err(_) => Lines::default(),
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    /// The byte offset of the next character.
+    position: usize,
+    /// Where the lines of `source` start, for [`Parser::lines`].
+    starts: Vec<usize>,
+    /// The keys and indexes of the value being parsed.
+    path: Vec<String>,
+    lines: Lines,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Parser {
+            source,
+            position: 0,
+            starts: line_starts(source),
+            path: Vec::new(),
+            lines: Lines::default(),
+        }
+    }
+
+    fn document(&mut self) -> Result<Value, Error> {
+        let value = self.value()?;
+        self.whitespace();
+        if self.peek().is_some() {
+            return Err(self.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    /// Records that the entry at `path` starts at the current position.
+    fn record(&mut self) {
+        let line = line_at(&self.starts, self.position);
+        self.lines.insert(&self.path, line);
+    }
+
+    fn error(&self, message: &str) -> Error {
+        let before = &self.source[..self.position];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        Error {
+            message: message.to_string(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.whitespace();
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("expected `{}`", expected)));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        self.whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Value::String),
+            Some('-' | '0'..='9') => self.number(),
+            Some(_) => {
+                for (literal, value) in [
+                    ("true", Value::Bool(true)),
+                    ("false", Value::Bool(false)),
+                    ("null", Value::Null),
+                ] {
+                    if self.source[self.position..].starts_with(literal) {
+                        self.position += literal.len();
+                        return Ok(value);
+                    }
+                }
+                Err(self.error("expected a value"))
+            }
+            None => Err(self.error("unexpected end of file, expected a value")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, Error> {
+        self.expect('{')?;
+        let mut mapping = Mapping::new();
+        self.whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Value::Mapping(mapping));
+        }
+        loop {
+            self.whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a string key"));
+            }
+            let start = self.position;
+            let key = self.string()?;
+            self.path.push(key.clone());
+            self.lines.insert(&self.path, line_at(&self.starts, start));
+            self.expect(':')?;
+            self.whitespace();
+            self.lines
+                .insert_value(&self.path, line_at(&self.starts, self.position));
+            let value = self.value()?;
+            self.path.pop();
+            mapping.insert(Value::String(key), value);
+            self.whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(Value::Mapping(mapping));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, Error> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            self.lines.set_length(&self.path, 0);
+            return Ok(Value::Sequence(values));
+        }
+        loop {
+            self.whitespace();
+            self.path.push(values.len().to_string());
+            self.record();
+            values.push(self.value()?);
+            self.path.pop();
+            self.whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    self.lines.set_length(&self.path, values.len());
+                    return Ok(Value::Sequence(values));
+                }
+                _ => return Err(self.error("expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let escaped = match self.peek() {
+                        Some(c @ ('"' | '\\' | '/')) => c,
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => {
+                            self.position += 1;
+                            string.push(self.unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("unknown escape character")),
+                    };
+                    self.position += 1;
+                    string.push(escaped);
+                }
+                Some(c) if c < ' ' => {
+                    self.position -= 1;
+                    return Err(self.error("control character in string"));
+                }
+                Some(c) => string.push(c),
+                None => return Err(self.error("unexpected end of file in string")),
+            }
+        }
+    }
+
+    /// The character of a `\uXXXX` escape, following it with the low surrogate if needed.
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let high = self.hex()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+        if !self.source[self.position..].starts_with("\\u") {
+            return Err(self.error("expected a low surrogate"));
+        }
+        self.position += 2;
+        let low = self.hex()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("expected a low surrogate"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex(&mut self) -> Result<u32, Error> {
+        let digits = self
+            .source
+            .get(self.position..self.position + 4)
+            .filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("expected four hex digits"))?;
+        self.position += 4;
+        Ok(u32::from_str_radix(digits, 16).expect("The digits are hex"))
+    }
+
+    fn number(&mut self) -> Result<Value, Error> {
+        let start = self.position;
+        let digits = |parser: &mut Self| {
+            let start = parser.position;
+            while let Some('0'..='9') = parser.peek() {
+                parser.position += 1;
+            }
+            parser.position > start
+        };
+        if self.peek() == Some('-') {
+            self.position += 1;
+        }
+        // No leading zeros
+        if self.peek() == Some('0') {
+            self.position += 1;
+            if let Some('0'..='9') = self.peek() {
+                return Err(self.error("invalid number"));
+            }
+        } else if !digits(self) {
+            return Err(self.error("invalid number"));
+        }
+        let mut float = false;
+        if self.peek() == Some('.') {
+            self.position += 1;
+            float = true;
+            if !digits(self) {
+                return Err(self.error("invalid number"));
+            }
+        }
+        if let Some('e' | 'E') = self.peek() {
+            self.position += 1;
+            float = true;
+            if let Some('+' | '-') = self.peek() {
+                self.position += 1;
+            }
+            if !digits(self) {
+                return Err(self.error("invalid number"));
+            }
+        }
+        let number = &self.source[start..self.position];
+        let number = match (float, number.parse::<i64>(), number.parse::<u64>()) {
+            (false, Ok(number), _) => Number::from(number),
+            (false, _, Ok(number)) => Number::from(number),
+            _ => Number::from(
+                number
+                    .parse::<f64>()
+                    .expect("A valid JSON number is a float"),
+            ),
+        };
+        Ok(Value::Number(number))
+    }
+}
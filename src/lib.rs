@@ -0,0 +1,2990 @@
+//! Generates vimscript or lua for neovim from YAML, TOML and JSON config files.
+
+mod backend;
+mod condition;
+mod env;
+mod error;
+mod events;
+mod extends;
+mod import;
+mod json;
+mod lines;
+mod manifest;
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::Display,
+    fs::{self, read_dir},
+    marker::PhantomData,
+    ops::Bound,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+pub use backend::Backend;
+use backend::{AutoCommandOptions, Body, MapOption, MapOptions};
+use condition::Condition;
+use enumflags2::{bitflags, BitFlags};
+pub use error::{to_json as errors_to_json, Error};
+pub use import::{import_vim, Import};
+use indexmap::IndexMap;
+use lines::Lines;
+use serde::{
+    de::DeserializeOwned,
+    de::{
+        value::{MapAccessDeserializer, SeqAccessDeserializer},
+        MapAccess, SeqAccess, Visitor,
+    },
+    Deserialize, Deserializer,
+};
+use serde_with::{serde_as, OneOrMany};
+
+/// Options of [`generate`].
+pub struct Options {
+    /// Language to generate.
+    pub backend: Backend,
+    /// Treat conflicting mappings as errors instead of warnings.
+    pub strict: bool,
+    /// Warn about unknown autocommand events instead of failing.
+    pub allow_unknown_events: bool,
+    /// Expand `${VAR}` in values, mapping rhs and autocommand commands.
+    pub expand_env: bool,
+    /// Split keybindings, autocommands and options off `plugin/config.<ext>` into
+    /// [`GeneratedFiles::plugin_files`].
+    pub split: bool,
+    /// Generate [`GeneratedFiles::manifest`].
+    pub manifest: bool,
+    /// Align the lhs and rhs of contiguous blocks of mappings.
+    pub format: bool,
+    /// Group the leader mappings of each set of flags in `keys` by their first key after the
+    /// leader, with a comment heading each group.
+    pub group_leader: bool,
+    /// Precede every mapping and abbreviation with a comment naming its config file and modes.
+    pub annotate: bool,
+}
+
+/// The defaults of `nvc`: the vim backend, with every other option off.
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            backend: Backend::Vim,
+            strict: false,
+            allow_unknown_events: false,
+            expand_env: false,
+            split: false,
+            manifest: false,
+            format: false,
+            group_leader: false,
+            annotate: false,
+        }
+    }
+}
+
+/// The sections [`Options::split`] splits off `plugin/config.<ext>`.
+const SPLIT_SECTIONS: &[&str] = &["keys", "autocmds", "options"];
+
+/// The contents of the generated files.
+pub struct GeneratedFiles {
+    /// `plugin/config.<ext>` (keyed by `None`) and `ftplugin/<ft>_config.<ext>` (keyed by the
+    /// file type), for each backend used, see [`Options::backend`].
+    pub files: HashMap<(Backend, Option<String>), String>,
+    /// Other `plugin/<name>.<ext>` files keyed by the backend and name: the sections of
+    /// [`Options::split`] and the `output` of config files.
+    pub plugin_files: HashMap<(Backend, String), String>,
+    /// The lazy.nvim spec for `lua/plugins/generated.lua`, only generated by the lua backend.
+    pub plugins: Option<String>,
+    /// A JSON array listing every mapping, autocommand, option etc. with the config file (and
+    /// line) it came from and the file it was written to, only generated with
+    /// [`Options::manifest`].
+    pub manifest: Option<String>,
+    /// How many mappings, autocommands, options etc. were generated, keyed by the kind used in
+    /// the manifest, e.g. `mapping` (counted once per mode).
+    pub counts: BTreeMap<&'static str, usize>,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AutoCommand {
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    triggers: Vec<String>,
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    cmd: Vec<AutoCommandCmd>,
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    lua: Vec<String>,
+    /// Defaults to `<buffer>` for file type autocommands and `*` otherwise.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    matching: Vec<String>,
+    #[serde(default)]
+    event: BTreeMap<String, String>,
+    #[serde(default)]
+    silent: bool,
+    #[serde(default)]
+    file_type: Option<String>,
+    /// Overrides the augroup, which defaults to one per config file.
+    group: Option<String>,
+    #[serde(default)]
+    once: bool,
+    #[serde(default)]
+    nested: bool,
+    /// Emitted as a comment above the autocommand.
+    comment: Option<String>,
+    /// Shown in `:autocmd`, the `desc` of the lua autocommand and a comment in vimscript.
+    desc: Option<String>,
+    /// Conditions that all need to hold, see [`Condition`].
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    when: Vec<Condition>,
+}
+
+/// An entry of [`AutoCommand::cmd`], either an ex command or a mapping it defines.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AutoCommandCmd {
+    Cmd(String),
+    Map(AutoCommandMapping),
+}
+
+/// A mapping defined when an autocommand runs, e.g. with the flags `nb` for a normal mode
+/// mapping in the buffer a `FileType` autocommand triggered in.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AutoCommandMapping {
+    /// The flags like in `keys`, except for the file type flag, parsed into
+    /// [`AutoCommandMapping::flags`] by [`Config::resolve_flags`].
+    map: String,
+    #[serde(skip)]
+    flags: MapFlags,
+    lhs: String,
+    rhs: String,
+}
+
+/// A parsed config file, see [`parse_config`] and [`parse_dir`].
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The file name, used in the generated comments and in error messages.
+    #[serde(skip)]
+    name: String,
+    /// The source text, used to find the lines of the [`manifest`].
+    #[serde(skip)]
+    text: String,
+    /// The format of `text`, see [`format_of`].
+    #[serde(skip)]
+    format: Option<String>,
+    /// Conditions that all need to hold for the whole file, see [`Condition`].
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    when: Vec<Condition>,
+    /// Emitted as a comment right after the file header.
+    description: Option<String>,
+    /// Writes the global output to `plugin/<output>.<ext>` instead of `plugin/config.<ext>`.
+    output: Option<String>,
+    /// Overrides [`Options::backend`] for this file, its output goes to the files of that backend.
+    backend: Option<Backend>,
+    #[serde(default, deserialize_with = "one_or_many_maps")]
+    auto_commands: Vec<AutoCommand>,
+    /// Augroups shared between files: the autocommands of every file in them (see
+    /// [`AutoCommand::group`]) are emitted together in one block after all files, which clears
+    /// the group once.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    augroups: Vec<String>,
+    /// Flags for every group in `keys` that gives no modes or no modifiers of its own, e.g. `nl`.
+    /// Applied after deserializing, see [`Config::resolve_flags`].
+    default_map_flags: Option<String>,
+    /// `g:mapleader`, set at the start of `plugin/config` before any mapping, whichever file sets
+    /// it, and of every other plugin file with leader mappings. Later files override earlier ones.
+    leader: Option<String>,
+    /// `g:maplocalleader`, like [`Config::leader`].
+    localleader: Option<String>,
+    /// `keys` as written, the flags are only parsed once `default_map_flags` is known.
+    #[serde(default, rename = "keys")]
+    raw_keys: IndexMap<String, MappingGroup>,
+    /// Set from `raw_keys` by [`Config::resolve_flags`].
+    #[serde(skip)]
+    keys: BTreeMap<MapFlags, MappingGroup>,
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    set: Vec<String>,
+    /// Emitted in the order written, as options can depend on the ones set before them.
+    #[serde(default)]
+    set_value: IndexMap<String, Value>,
+    /// Options reset to their default (`set name&`), before `set` and `set_value`.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    set_default: Vec<String>,
+    /// Emitted in the order written, like `set_value`.
+    #[serde(default)]
+    r#let: IndexMap<String, Value>,
+    /// Variables removed (`unlet!`), before `let`.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    unlet: Vec<String>,
+    #[serde(default)]
+    file_type: BTreeMap<String, FileTypeOptions>,
+    #[serde(default)]
+    commands: BTreeMap<String, UserCommand>,
+    /// `abbreviations` as written, parsed like `raw_keys`.
+    #[serde(default, rename = "abbreviations")]
+    raw_abbreviations: IndexMap<String, BTreeMap<String, Abbreviation>>,
+    /// Set from `raw_abbreviations` by [`Config::resolve_flags`], with the flags as written.
+    #[serde(skip)]
+    abbreviations: BTreeMap<AbbreviationFlags, (String, BTreeMap<String, Abbreviation>)>,
+    /// Only generated by the lua backend.
+    #[serde(default)]
+    plugins: Vec<Plugin>,
+    #[serde(default)]
+    highlights: BTreeMap<String, Highlight>,
+    /// Files relative to the directory of the config file, included verbatim after the generated sections.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    source: Vec<Source>,
+}
+
+/// A file of `source`, its content is read by [`parse_config`].
+#[derive(Deserialize)]
+#[serde(from = "String")]
+struct Source {
+    path: String,
+    content: String,
+}
+
+impl From<String> for Source {
+    fn from(path: String) -> Self {
+        Source {
+            path,
+            content: String::new(),
+        }
+    }
+}
+
+/// A highlight group, either linking to another group or defining its GUI colors and style.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Highlight {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    bold: bool,
+    #[serde(default)]
+    italic: bool,
+    #[serde(default)]
+    underline: bool,
+    link: Option<String>,
+}
+
+/// A plugin spec for lazy.nvim.
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Plugin {
+    /// The GitHub `user/repo` of the plugin.
+    repo: String,
+    lazy: Option<bool>,
+    /// Load the plugin on these events.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    event: Vec<String>,
+    /// Load the plugin for these file types.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    ft: Vec<String>,
+    /// Lua run after the plugin is loaded.
+    config: Option<String>,
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    dependencies: Vec<String>,
+}
+
+/// A `:command`, with either a vimscript `rhs` or a `lua` body.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UserCommand {
+    rhs: Option<String>,
+    lua: Option<String>,
+    nargs: Option<CommandAttribute>,
+    range: Option<CommandAttribute>,
+    /// Mutually exclusive with `range`.
+    count: Option<CommandAttribute>,
+    /// What the range refers to, one of [`ADDRESS_TYPES`].
+    addr: Option<String>,
+    #[serde(default)]
+    bang: bool,
+    complete: Option<String>,
+}
+
+/// The values of `-addr` of a user command, with their short names.
+const ADDRESS_TYPES: &[&str] = &[
+    "lines",
+    "line",
+    "arguments",
+    "arg",
+    "buffers",
+    "buf",
+    "loaded_buffers",
+    "load",
+    "windows",
+    "win",
+    "tabs",
+    "tab",
+    "quickfix",
+    "qf",
+    "other",
+    "?",
+];
+
+/// The value of a `-name=value` attribute of a user command, `true` emits just `-name`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CommandAttribute {
+    Bool(bool),
+    Int(i64),
+    String(String),
+}
+impl Display for CommandAttribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandAttribute::Bool(value) => write!(f, "{value}"),
+            CommandAttribute::Int(value) => write!(f, "{value}"),
+            CommandAttribute::String(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Like `OneOrMany`, but keeps the errors of the elements instead of replacing them with a
+/// generic mismatch, so e.g. unknown fields are still reported.
+fn one_or_many_maps<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct OneOrManyVisitor<T>(PhantomData<T>);
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for OneOrManyVisitor<T> {
+        type Value = Vec<T>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a map or a list of maps")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+            Vec::deserialize(SeqAccessDeserializer::new(seq))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            T::deserialize(MapAccessDeserializer::new(map)).map(|value| vec![value])
+        }
+    }
+    deserializer.deserialize_any(OneOrManyVisitor(PhantomData))
+}
+
+/// The mappings of one set of [`MapFlags`] in `keys`.
+#[derive(Deserialize)]
+struct MappingGroup {
+    /// Only define the mappings if this lua module can be required, e.g. `telescope`, so they
+    /// do not fail while the plugin is not installed.
+    requires_plugin: Option<String>,
+    #[serde(flatten)]
+    mappings: BTreeMap<String, MaybePrefixedMapping>,
+    /// The flags each of `mappings` is written under, as equivalent flags share one group.
+    #[serde(skip)]
+    written_flags: HashMap<String, String>,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileTypeOptions {
+    #[serde(default)]
+    #[serde_as(deserialize_as = "OneOrMany<_>")]
+    set: Vec<String>,
+    /// Like [`Config::set_value`], but with `setlocal`, so booleans become `setlocal spell` and
+    /// `setlocal nospell`.
+    #[serde(default)]
+    set_value: IndexMap<String, Value>,
+    #[serde(default)]
+    r#let: IndexMap<String, Value>,
+}
+
+/// An entry of `let`, either a variable or the variables of a scope (`global`, `buffer`,
+/// `window` or `tab`), given as a map keyed by the scope.
+enum LetEntry<'a> {
+    Variable(&'a Value),
+    Scoped(&'static str, &'a IndexMap<String, Value>),
+}
+
+impl<'a> LetEntry<'a> {
+    /// A map is only a scope if `name` is one, otherwise it is a dictionary.
+    fn new(name: &str, value: &'a Value) -> Self {
+        match (variable_scope(name), value) {
+            (Some(scope), Value::Map(entries)) => LetEntry::Scoped(scope, entries),
+            _ => LetEntry::Variable(value),
+        }
+    }
+}
+
+/// The variable prefix (without the `:`) of a scope in `let`.
+fn variable_scope(scope: &str) -> Option<&'static str> {
+    match scope {
+        "global" | "g" => Some("g"),
+        "buffer" | "b" => Some("b"),
+        "window" | "w" => Some("w"),
+        "tab" | "t" => Some("t"),
+        _ => None,
+    }
+}
+
+/// The variables assigned by `entries`, with unscoped names prefixed by `default_scope`, and the
+/// keys they are written at.
+fn variables<'a>(
+    entries: &'a IndexMap<String, Value>,
+    default_scope: Option<&str>,
+) -> Vec<(String, Value, Vec<&'a str>)> {
+    let mut variables = Vec::new();
+    for (name, value) in entries {
+        match LetEntry::new(name, value) {
+            LetEntry::Variable(value) => {
+                let variable = match default_scope {
+                    Some(scope) if !name.contains(':') => format!("{}:{}", scope, name),
+                    _ => name.clone(),
+                };
+                variables.push((variable, value.clone(), vec![name.as_str()]));
+            }
+            LetEntry::Scoped(scope, entries) => {
+                variables.extend(entries.iter().map(|(variable, value)| {
+                    (
+                        format!("{}:{}", scope, variable),
+                        value.clone(),
+                        vec![name.as_str(), variable.as_str()],
+                    )
+                }));
+            }
+        }
+    }
+    variables
+}
+
+/// Checks the scopes used in `entries`.
+fn validate_let(entries: &IndexMap<String, Value>, filename: &str) -> Vec<anyhow::Error> {
+    let mut errors = Vec::new();
+    for (name, value) in entries {
+        match LetEntry::new(name, value) {
+            LetEntry::Variable(_) => {
+                if let Some((scope, _)) = name.split_once(':') {
+                    if scope.len() != 1 || variable_scope(scope).is_none() {
+                        errors.push(validation_error(filename, format!(
+                            "Unsupported scope `{}:` of variable `{}` in {} (supported: g:, b:, w:, t:)",
+                            scope,
+                            name,
+                            filename
+                        )));
+                    }
+                }
+            }
+            LetEntry::Scoped(_, entries) => {
+                for variable in entries.keys().filter(|variable| variable.contains(':')) {
+                    errors.push(validation_error(
+                        filename,
+                        format!(
+                            "Variable `{}` in scope `{}` in {} must not have a scope prefix",
+                            variable, name, filename
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Checks that the values in `set_value` can be expressed by `set`, which ends at a line break.
+fn validate_set_value(entries: &IndexMap<String, Value>, filename: &str) -> Vec<anyhow::Error> {
+    fn has_line_break(value: &Value) -> bool {
+        match value {
+            Value::String(value) | Value::Expr(Expression { expr: value }) => value.contains('\n'),
+            Value::List(values) => values.iter().any(has_line_break),
+            Value::Map(values) => values.values().any(has_line_break),
+            Value::Int(_) | Value::Float(_) | Value::Bool(_) => false,
+        }
+    }
+    fn has_expr(value: &Value) -> bool {
+        match value {
+            Value::Expr(_) => true,
+            Value::List(values) => values.iter().any(has_expr),
+            Value::Map(values) => values.values().any(has_expr),
+            _ => false,
+        }
+    }
+    let line_breaks = entries
+        .iter()
+        .filter(|(_, value)| has_line_break(value))
+        .map(|(name, _)| {
+            validation_error(
+                filename,
+                format!(
+                    "Option `{}` in {} contains a line break, which `set` cannot express (use a \
+                 folded block scalar `>-` to write long values on multiple lines)",
+                    name, filename
+                ),
+            )
+        });
+    // Option values are either set from an expression as a whole or written out
+    let nested_exprs = entries
+        .iter()
+        .filter(|(_, value)| !matches!(value, Value::Expr(_)) && has_expr(value))
+        .map(|(name, _)| {
+            validation_error(
+                filename,
+                format!(
+                    "Option `{}` in {} contains an `expr` inside a list or map, only the whole \
+                     value can be an expression",
+                    name, filename
+                ),
+            )
+        });
+    line_breaks.chain(nested_exprs).collect()
+}
+
+/// Checks that no option is given in both `set` and `set_value`, which would emit two `set` lines
+/// overriding each other.
+fn validate_set_conflicts(
+    set: &[String],
+    set_value: &IndexMap<String, Value>,
+    filename: &str,
+) -> Vec<anyhow::Error> {
+    set.iter()
+        .flat_map(|set| set.split_whitespace())
+        .filter_map(|option| {
+            // `tabstop=4`, `shiftwidth+=2`, `list!` or `ruler&`
+            let name = option
+                .split(|c: char| "=+-^!&?:".contains(c))
+                .next()
+                .unwrap_or_default();
+            // `nowrap` and `invlist` toggle `wrap` and `list`
+            [
+                Some(name),
+                name.strip_prefix("no"),
+                name.strip_prefix("inv"),
+            ]
+            .iter()
+            .flatten()
+            .find(|name| set_value.contains_key(**name))
+            .map(|name| {
+                validation_error(
+                    filename,
+                    format!(
+                        "Option `{}` in {} is given in both `set` (as `{}`) and `set_value`",
+                        name, filename, option
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
+    List(Vec<Value>),
+    Expr(Expression),
+    Map(IndexMap<String, Value>),
+}
+
+/// A vimscript expression evaluated when the config is loaded, given as `{expr: ...}` in place of
+/// a value, e.g. `{expr: "&columns / 2"}`. Unlike strings, it is emitted without any quoting or
+/// escaping.
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Expression {
+    expr: String,
+}
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(value) => write!(f, "{value}"),
+            // `Debug` is the shortest representation that round-trips, but vimscript requires
+            // a `.` in every float, even with an exponent
+            Value::Float(value) => match format!("{:?}", value) {
+                value if value.contains('e') && !value.contains('.') => {
+                    write!(f, "{}", value.replacen('e', ".0e", 1))
+                }
+                value => write!(f, "{}", value),
+            },
+            Value::String(value) => f.write_str(&backend::vim_string(value)),
+            Value::Expr(Expression { expr }) => f.write_str(expr),
+            Value::Bool(true) => write!(f, "v:true"),
+            Value::Bool(false) => write!(f, "v:false"),
+            Value::List(values) => write!(
+                f,
+                "[{}]",
+                values
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Value::Map(entries) => write!(
+                f,
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|(key, value)| format!("'{}': {}", key.replace('\'', "''"), value))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+fn augroup_name(filename: &str) -> String {
+    filename
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[bitflags]
+#[repr(u16)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum MapFlag {
+    Insert,
+    Normal,
+    Visual,
+    Select,
+    OperatorPending,
+    Terminal,
+    Leader,
+    LocalLeader,
+    /// Wraps the rhs in `<CMD>...<CR>`, not to be confused with [`MapFlag::CommandLine`].
+    CmdWrap,
+    Recursive,
+    Expr,
+    Unsilent,
+    Buffer,
+    /// Command-line mode (`cnoremap`).
+    CommandLine,
+    /// Trigger without waiting for longer mappings starting with the lhs (`<nowait>`).
+    NoWait,
+    /// Map to `<Nop>`, disabling the (default) mapping of the lhs.
+    Disable,
+}
+
+#[derive(Default, Hash, PartialEq, Eq)]
+struct MapFlags {
+    flags: BitFlags<MapFlag>,
+    file_type: Option<String>,
+    label: Option<String>,
+}
+
+/// The modes of [`MapFlag`], the other flags are modifiers.
+fn mode_flags() -> BitFlags<MapFlag> {
+    use MapFlag::*;
+    Insert | Normal | Visual | Select | OperatorPending | Terminal | CommandLine
+}
+
+impl MapFlags {
+    /// The flags of `key` with the `default_map_flags` `default`.
+    fn parse(key: &str, default: BitFlags<MapFlag>) -> Result<Self> {
+        let mut flags = MapFlags::parse_letters(key)?;
+        // Modes and modifiers of a group each replace the default ones
+        if (flags.flags & mode_flags()).is_empty() {
+            flags.flags |= default & mode_flags();
+        }
+        if (flags.flags & !mode_flags()).is_empty() {
+            flags.flags |= default & !mode_flags();
+        }
+        flags.check(key)?;
+        // Without a mode nothing would be mapped
+        if map_modes(flags.flags).is_empty() {
+            bail!(
+                "Mapping group `{}` has no mode flags (supported modes: i, n, v, s, o, t, m (command line))",
+                key
+            );
+        }
+        Ok(flags)
+    }
+
+    /// The flags as written in `key`, without the `default_map_flags` or any checks.
+    fn parse_letters(key: &str) -> Result<Self> {
+        use MapFlag::*;
+        // Duplicate letters like `nn` are harmless
+        let mut flags = BitFlags::empty();
+        let (s, mut label) = match key.split_once("_") {
+            Some((s, label)) => (s, Some(label.to_string())),
+            None => (key, None),
+        };
+        let mut file_type = None;
+
+        for c in s.to_ascii_lowercase().chars() {
+            flags |= match c {
+                'i' => Insert,
+                'n' => Normal,
+                'v' => Visual,
+                's' => Select,
+                'o' => OperatorPending,
+                't' => Terminal,
+                'l' => Leader,
+                'k' => LocalLeader,
+                'm' => CommandLine,
+                // `c` is the original letter of the wrapping flag
+                'w' | 'c' => CmdWrap,
+                'r' => Recursive,
+                'e' => Expr,
+                'u' => Unsilent,
+                'b' => Buffer,
+                'a' => NoWait,
+                'x' => Disable,
+                'f' => match (label, file_type) {
+                    (Some(l), None) => {
+                        let (ft, l) = match l.split_once("_") {
+                            Some((ft, l)) => (ft, Some(l.to_string())),
+                            None => (l.as_str(), None),
+                        };
+                        // `nf_` or `nf__label` would map for the file type ``
+                        if ft.is_empty() {
+                            bail!("Filetype flag needs a filetype after the `_`: `{}`", key);
+                        }
+                        file_type = Some(ft.to_string());
+                        label = l;
+                        continue;
+                    }
+                    (_, Some(_)) => bail!("Duplicate filetype flag not supported: `{}`", s),
+                    (None, _) => bail!("Filetype flag only supported when filetype is given"),
+                },
+                _ => bail!(
+                    "Unsuported flag for Mapping: `{}` (supported modes: i, n, v, s, o, t, m (command line); modifiers: l, k, w or c (wrap rhs in <CMD>...<CR>), r, e, u, b, a (nowait), x (disable), f)",
+                    c
+                ),
+            };
+        }
+        Ok(MapFlags {
+            flags,
+            label,
+            file_type,
+        })
+    }
+
+    /// Errors on mutually exclusive flags.
+    fn check(&self, key: &str) -> Result<()> {
+        use MapFlag::*;
+        let flags = self.flags;
+        let s = key.split_once('_').map_or(key, |(s, _)| s);
+        if flags.contains(Leader | LocalLeader) {
+            bail!(
+                "Leader (`l`) and local leader (`k`) flags are mutually exclusive: `{}`",
+                s
+            );
+        }
+        if flags.contains(CmdWrap | Expr) {
+            bail!(
+                "Command wrapping (`w` or `c`) and expression (`e`) flags are mutually exclusive: `{}`",
+                s
+            );
+        }
+        if flags.contains(Disable) && flags.intersects(CmdWrap | Expr) {
+            bail!(
+                "Disable (`x`) flag is mutually exclusive with command wrapping (`w` or `c`) and expression (`e`): `{}`",
+                s
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Like [`MapFlags`], but for abbreviations: `i` (insert), `c` (command line), `r`
+/// (recursive), `b` (buffer) and `f` (file type, given after an `_`).
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct AbbreviationFlags {
+    insert: bool,
+    command: bool,
+    recursive: bool,
+    buffer: bool,
+    file_type: Option<String>,
+}
+
+impl AbbreviationFlags {
+    fn parse(s: &str) -> Result<Self> {
+        let (s, file_type) = match s.split_once('_') {
+            Some((s, file_type)) => (s, Some(file_type)),
+            None => (s, None),
+        };
+        let mut flags = AbbreviationFlags {
+            insert: false,
+            command: false,
+            recursive: false,
+            buffer: false,
+            file_type: None,
+        };
+        for c in s.to_ascii_lowercase().chars() {
+            match c {
+                'i' => flags.insert = true,
+                'c' => flags.command = true,
+                'r' => flags.recursive = true,
+                'b' => flags.buffer = true,
+                'f' => match file_type {
+                    Some(file_type) => flags.file_type = Some(file_type.to_string()),
+                    None => bail!("Filetype flag only supported when filetype is given"),
+                },
+                _ => bail!(
+                    "Unsuported flag for Abbreviation: `{}` (supported modes: i, c; modifiers: r, b, f)",
+                    c
+                ),
+            }
+        }
+        if !flags.insert && !flags.command {
+            bail!("Abbreviation needs a mode (`i` or `c`): `{}`", s);
+        }
+        if flags.file_type.is_none() && file_type.is_some() {
+            bail!("Filetype given without filetype flag (`f`): `{}`", s);
+        }
+        Ok(flags)
+    }
+}
+
+/// The rhs of an abbreviation, either expanded unconditionally or only if `expr` holds.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Abbreviation {
+    Rhs(String),
+    Guarded {
+        rhs: String,
+        expr: AbbreviationGuard,
+    },
+}
+
+/// When a guarded [`Abbreviation`] expands: `true` only at the start of an ex command line (the
+/// `cnoreabbrev W w` typo fix), or a vimscript expression.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AbbreviationGuard {
+    CommandStart(bool),
+    Expr(String),
+}
+
+impl Abbreviation {
+    /// The rhs, an `<expr>` if the abbreviation is guarded.
+    fn rhs(&self, lhs: &str) -> (String, bool) {
+        let (rhs, guard) = match self {
+            Abbreviation::Rhs(rhs)
+            | Abbreviation::Guarded {
+                rhs,
+                expr: AbbreviationGuard::CommandStart(false),
+            } => return (rhs.clone(), false),
+            Abbreviation::Guarded {
+                rhs,
+                expr: AbbreviationGuard::CommandStart(true),
+            } => (
+                rhs,
+                format!(
+                    "getcmdtype() ==# ':' && getcmdline() ==# {}",
+                    backend::vim_string(lhs)
+                ),
+            ),
+            Abbreviation::Guarded {
+                rhs,
+                expr: AbbreviationGuard::Expr(guard),
+            } => (rhs, guard.trim().to_string()),
+        };
+        let expr = format!(
+            "{} ? {} : {}",
+            guard,
+            backend::vim_string(rhs),
+            backend::vim_string(lhs)
+        );
+        (expr, true)
+    }
+}
+
+/// The mode letters of the modes in `flags`, `c` being the command line.
+fn map_modes(flags: BitFlags<MapFlag>) -> Vec<char> {
+    [
+        (MapFlag::Insert, 'i'),
+        (MapFlag::Normal, 'n'),
+        (MapFlag::Visual, 'v'),
+        (MapFlag::Select, 's'),
+        (MapFlag::OperatorPending, 'o'),
+        (MapFlag::Terminal, 't'),
+        (MapFlag::CommandLine, 'c'),
+    ]
+    .iter()
+    .filter(|(flag, _)| flags.contains(*flag))
+    .map(|(_, mode)| *mode)
+    .collect()
+}
+
+/// The lhs of the mapping `key` with `flags`, prefixed by the (local) leader if requested.
+fn leader_lhs(flags: BitFlags<MapFlag>, key: &str) -> String {
+    let key = lhs(key);
+    if flags.contains(MapFlag::Leader) {
+        format!("<LEADER>{}", key)
+    } else if flags.contains(MapFlag::LocalLeader) {
+        format!("<LOCALLEADER>{}", key)
+    } else {
+        key
+    }
+}
+
+/// The modifiers of mappings with `flags`.
+fn map_options(flags: BitFlags<MapFlag>) -> MapOptions {
+    MapOptions {
+        recursive: flags.contains(MapFlag::Recursive),
+        // Mappings are silent unless explicitly requested otherwise, except in the command line,
+        // where silent mappings would not redraw it
+        silent: !flags.contains(MapFlag::Unsilent) && !flags.contains(MapFlag::CommandLine),
+        expr: flags.contains(MapFlag::Expr),
+        buffer: flags.contains(MapFlag::Buffer),
+        nowait: flags.contains(MapFlag::NoWait),
+        ..MapOptions::default()
+    }
+}
+
+impl Ord for MapFlags {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.file_type, &self.label, self.flags.bits()).cmp(&(
+            &other.file_type,
+            &other.label,
+            other.flags.bits(),
+        ))
+    }
+}
+
+impl PartialOrd for MapFlags {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum MaybePrefixedMapping {
+    Mapping(String),
+    /// `null`, mapping to `<Nop>`.
+    Nop,
+    Labeled(LabeledBinding),
+    PrefixedMappings(BTreeMap<String, Binding>),
+}
+
+/// The rhs of a mapping, either shared by all modes or given per mode (keyed by the mode letters
+/// of [`MapFlags`], e.g. `n` or `iv`).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(untagged)]
+enum Rhs {
+    Shared(String),
+    PerMode(BTreeMap<String, String>),
+    /// `null`, mapping to `<Nop>` in every mode.
+    Nop,
+}
+
+/// Marks the rhs of a mapping as a lua function (reference) to call, like
+/// `lua: require('telescope.builtin').find_files`.
+const LUA_RHS: &str = "lua:";
+
+/// Whether `rhs` triggers a `<Plug>` mapping of a plugin, which only works with `map`, never with
+/// `noremap`.
+fn is_plug(rhs: &str) -> bool {
+    rhs.get(..6)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("<Plug>"))
+}
+
+impl Rhs {
+    /// Whether any mode maps to a lua function, see [`LUA_RHS`].
+    fn is_lua(&self) -> bool {
+        match self {
+            Rhs::Shared(rhs) => rhs.starts_with(LUA_RHS),
+            Rhs::PerMode(per_mode) => per_mode.values().any(|rhs| rhs.starts_with(LUA_RHS)),
+            Rhs::Nop => false,
+        }
+    }
+
+    /// The rhs for each of `modes`, grouping modes sharing the same rhs, in the order of `modes`.
+    fn per_mode(&self, modes: &[char]) -> Result<Vec<(Vec<char>, String)>> {
+        let per_mode = match self {
+            Rhs::Shared(rhs) => return Ok(vec![(modes.to_vec(), rhs.clone())]),
+            Rhs::Nop => return Ok(vec![(modes.to_vec(), "<Nop>".to_string())]),
+            Rhs::PerMode(per_mode) => per_mode,
+        };
+        let mut rhs_of = BTreeMap::new();
+        for (letters, rhs) in per_mode {
+            for letter in letters.to_ascii_lowercase().chars() {
+                let mode = match letter {
+                    'i' | 'n' | 'v' | 's' | 'o' | 't' => letter,
+                    'm' => 'c',
+                    _ => bail!(
+                        "Unsupported mode `{}` of rhs (supported: i, n, v, s, o, t, m)",
+                        letter
+                    ),
+                };
+                if !modes.contains(&mode) {
+                    bail!("Rhs given for mode `{}` that is not mapped", letter);
+                }
+                if rhs_of.insert(mode, rhs).is_some() {
+                    bail!("Rhs given twice for mode `{}`", letter);
+                }
+            }
+        }
+        let mut grouped: Vec<(Vec<char>, String)> = Vec::new();
+        for mode in modes {
+            let rhs = rhs_of
+                .get(mode)
+                .with_context(|| format!("Missing rhs for mode `{}`", mode))?;
+            match grouped.iter_mut().find(|(_, existing)| existing == *rhs) {
+                Some((modes, _)) => modes.push(*mode),
+                None => grouped.push((vec![*mode], rhs.to_string())),
+            }
+        }
+        Ok(grouped)
+    }
+}
+
+/// The rhs of a prefixed mapping, optionally with its own label, or a nested prefix.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Binding {
+    Rhs(String),
+    /// `null`, mapping to `<Nop>`.
+    Nop,
+    Labeled(LabeledBinding),
+    LabeledPrefix(LabeledPrefix),
+    Prefix(BTreeMap<String, Binding>),
+}
+
+/// A nested prefix with a label for the whole group.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LabeledPrefix {
+    label: String,
+    keys: BTreeMap<String, Binding>,
+}
+
+/// A flattened mapping.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct FlatBinding {
+    lhs: String,
+    /// The keys of the mapping as written below its flags, e.g. `<leader>`, `f` for `f` nested
+    /// in the prefix `<leader>`.
+    path: Vec<String>,
+    rhs: Rhs,
+    /// The label of a prefixed mapping, `Some(None)` if it has none.
+    label: Option<Option<String>>,
+    /// The labels of the groups starting at this mapping.
+    group_labels: Vec<String>,
+    /// Overrides whether the mapping is silent.
+    silent: Option<bool>,
+    /// Modifiers added to the ones of the flags, parsed into [`MapOption`]s while generating.
+    opts: Vec<String>,
+}
+
+impl FlatBinding {
+    fn new(path: Vec<String>, lhs: String, rhs: Rhs, label: Option<Option<String>>) -> Self {
+        Self {
+            lhs,
+            path,
+            rhs,
+            label,
+            group_labels: Vec::new(),
+            silent: None,
+            opts: Vec::new(),
+        }
+    }
+}
+
+/// Flattens the mappings of one set of [`MapFlags`], sorted by their lhs.
+fn flat_bindings(mappings: &BTreeMap<String, MaybePrefixedMapping>) -> Vec<FlatBinding> {
+    let mut flat = Vec::new();
+    for (key, mapping) in mappings {
+        let path = vec![key.clone()];
+        match mapping {
+            MaybePrefixedMapping::Mapping(rhs) => {
+                flat.push(FlatBinding::new(
+                    path,
+                    key.clone(),
+                    Rhs::Shared(rhs.clone()),
+                    None,
+                ));
+            }
+            MaybePrefixedMapping::Nop => {
+                flat.push(FlatBinding::new(path, key.clone(), Rhs::Nop, None))
+            }
+            MaybePrefixedMapping::Labeled(LabeledBinding {
+                rhs,
+                label,
+                silent,
+                opts,
+            }) => {
+                // Without a label, the one of the flags is kept
+                let label = label.clone().map(Some);
+                flat.push(FlatBinding {
+                    silent: *silent,
+                    opts: opts.clone(),
+                    ..FlatBinding::new(path, key.clone(), rhs.clone(), label)
+                });
+            }
+            MaybePrefixedMapping::PrefixedMappings(bindings) => {
+                flatten_bindings(&path, key, bindings, None, &mut flat);
+            }
+        }
+    }
+    flat.sort();
+    flat
+}
+
+/// Flattens the possibly nested `bindings` below `prefix` (written at `path`) into `flat`, the
+/// label of the group is attached to its first mapping.
+fn flatten_bindings(
+    path: &[String],
+    prefix: &str,
+    bindings: &BTreeMap<String, Binding>,
+    group_label: Option<&String>,
+    flat: &mut Vec<FlatBinding>,
+) {
+    let start = flat.len();
+    for (suffix, binding) in bindings {
+        let key = format!("{}{}", prefix, suffix);
+        let mut path = path.to_vec();
+        path.push(suffix.clone());
+        match binding {
+            Binding::Rhs(rhs) => flat.push(FlatBinding::new(
+                path,
+                key,
+                Rhs::Shared(rhs.clone()),
+                Some(None),
+            )),
+            Binding::Nop => flat.push(FlatBinding::new(path, key, Rhs::Nop, Some(None))),
+            Binding::Labeled(LabeledBinding {
+                rhs,
+                label,
+                silent,
+                opts,
+            }) => flat.push(FlatBinding {
+                silent: *silent,
+                opts: opts.clone(),
+                ..FlatBinding::new(path, key, rhs.clone(), Some(label.clone()))
+            }),
+            Binding::LabeledPrefix(LabeledPrefix { label, keys }) => {
+                path.push("keys".to_string());
+                flatten_bindings(&path, &key, keys, Some(label), flat)
+            }
+            Binding::Prefix(bindings) => flatten_bindings(&path, &key, bindings, None, flat),
+        }
+    }
+    if let (Some(label), Some(first)) = (group_label, flat.get_mut(start)) {
+        first.group_labels.insert(0, label.clone());
+    }
+}
+
+/// The prefixes of `mappings` with a label, i.e. the prefixed mappings of a set of [`MapFlags`]
+/// with a label and every [`LabeledPrefix`], as (lhs, label).
+fn prefix_groups(
+    mappings: &BTreeMap<String, MaybePrefixedMapping>,
+    label: Option<&String>,
+) -> Vec<(String, String)> {
+    fn nested(
+        prefix: &str,
+        bindings: &BTreeMap<String, Binding>,
+        groups: &mut Vec<(String, String)>,
+    ) {
+        for (suffix, binding) in bindings {
+            let key = format!("{}{}", prefix, suffix);
+            match binding {
+                Binding::LabeledPrefix(LabeledPrefix { label, keys }) => {
+                    groups.push((key.clone(), label.clone()));
+                    nested(&key, keys, groups);
+                }
+                Binding::Prefix(bindings) => nested(&key, bindings, groups),
+                _ => {}
+            }
+        }
+    }
+    let mut groups = Vec::new();
+    for (key, mapping) in mappings {
+        if let MaybePrefixedMapping::PrefixedMappings(bindings) = mapping {
+            if let Some(label) = label {
+                groups.push((key.clone(), label.clone()));
+            }
+            nested(key, bindings, &mut groups);
+        }
+    }
+    groups
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LabeledBinding {
+    rhs: Rhs,
+    label: Option<String>,
+    /// Overrides whether this mapping is silent, e.g. `false` in an otherwise silent group.
+    silent: Option<bool>,
+    /// Modifiers by name, e.g. `[unique, script]`, added to the ones of the flags.
+    #[serde(default)]
+    opts: Vec<String>,
+}
+
+/// Adds the 1-based `location` and the offending line of `source` to a parse error.
+fn parse_error<E>(
+    error: E,
+    location: Option<(usize, usize)>,
+    filename: &str,
+    source: &str,
+) -> anyhow::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let message = parse_message(location, filename, source);
+    anyhow::Error::new(error).context(Error::Parse {
+        file: filename.to_string(),
+        message,
+        location,
+    })
+}
+
+/// "Failed to parse file" with the 1-based `location` and the offending line of `source`.
+fn parse_message(location: Option<(usize, usize)>, filename: &str, source: &str) -> String {
+    match location {
+        Some((line, column)) => {
+            let snippet = line
+                .checked_sub(1)
+                .and_then(|line| source.lines().nth(line))
+                .unwrap_or_default();
+            format!(
+                "Failed to parse file: {}:{}:{}\n{:>4} | {}\n     | {:>column$}",
+                filename,
+                line,
+                column,
+                line,
+                snippet,
+                "^",
+                column = column
+            )
+        }
+        None => format!("Failed to parse file: {}", filename),
+    }
+}
+
+/// A [`Error::Validation`] of `file`.
+fn validation_error(file: &str, message: String) -> anyhow::Error {
+    Error::Validation {
+        file: file.to_string(),
+        message,
+    }
+    .into()
+}
+
+/// The mappings defined so far: (file type, buffer local, mode, lhs) -> (file defining it,
+/// nowait).
+type DefinedMappings = HashMap<(Option<String>, bool, char, String), (String, bool)>;
+
+/// Warns about mappings without `<nowait>` whose lhs is a prefix of another mapping in the same
+/// mode, as they only trigger after `timeoutlen`. Uses the mappings collected by [`generate`].
+fn warn_prefixes(mappings: &DefinedMappings) {
+    let mut scopes: BTreeMap<_, BTreeMap<_, _>> = BTreeMap::new();
+    for ((file_type, buffer, mode, lhs), defined) in mappings {
+        scopes
+            .entry((file_type, *buffer, *mode))
+            .or_default()
+            .insert(lhs, defined);
+    }
+    for ((_, _, mode), lhss) in scopes {
+        for (lhs, (filename, nowait)) in &lhss {
+            if *nowait {
+                continue;
+            }
+            // In lexical order, longer mappings starting with `lhs` directly follow it
+            let longer = lhss
+                .range::<&String, _>((Bound::Excluded(lhs), Bound::Unbounded))
+                .next()
+                .filter(|(longer, _)| longer.starts_with(lhs.as_str()));
+            if let Some((longer, (longer_filename, _))) = longer {
+                eprintln!(
+                    "Warning: Mapping `{}` in mode `{}` in {} is a prefix of `{}` in {} and waits for \
+                     `timeoutlen`, consider the nowait flag (`a`)",
+                    lhs, mode, filename, longer, longer_filename
+                );
+            }
+        }
+    }
+}
+
+/// The runtime checks of `conditions` as expressions, or `None` if a condition checked while
+/// generating does not hold.
+fn runtime_checks(backend: Backend, conditions: &[Condition]) -> Option<Vec<String>> {
+    let mut guards = Vec::new();
+    for condition in conditions {
+        match condition.evaluate() {
+            Some(false) => return None,
+            Some(true) => {}
+            None => guards.push(backend.has(&condition.features())),
+        }
+    }
+    Some(guards)
+}
+
+/// Trims the lhs of a mapping and spells out the whitespace inside it (as `<Space>` and
+/// `<Tab>`), which would otherwise end the lhs in vimscript, so e.g. `g g` maps `g<Space>g`.
+/// Key notation like `<C-Space>` or `<lt>` is kept, `\<` is a literal `<` (`<lt>`), for keys
+/// like `<a>` that would otherwise be read as key notation.
+fn lhs(key: &str) -> String {
+    let mut lhs = String::with_capacity(key.len());
+    let mut chars = key.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' => lhs.push_str("<Space>"),
+            '\t' => lhs.push_str("<Tab>"),
+            '\\' if chars.peek() == Some(&'<') => {
+                chars.next();
+                lhs.push_str("<lt>");
+            }
+            c => lhs.push(c),
+        }
+    }
+    lhs
+}
+
+/// The first key of `lhs`, either a single character or key notation like `<C-x>`.
+fn first_key(lhs: &str) -> &str {
+    let notation = lhs
+        .strip_prefix('<')
+        .and_then(|rest| rest.find('>'))
+        .filter(|&end| end > 0)
+        .map(|end| end + 2);
+    let end = notation.unwrap_or_else(|| lhs.chars().next().map_or(0, char::len_utf8));
+    &lhs[..end]
+}
+
+/// Expands the brace groups of an autocommand pattern, e.g. `*.{c,h}` to `*.c` and `*.h`. Groups
+/// can be nested and a group without a `,` keeps its braces (its nested groups are expanded), like
+/// in a shell.
+fn expand_braces(pattern: &str) -> Result<Vec<String>> {
+    // The first group and the top-level commas inside it
+    let mut open = None;
+    let mut commas = Vec::new();
+    let mut depth = 0;
+    let mut escaped = false;
+    for (index, c) in pattern.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '{' => {
+                if depth == 0 {
+                    open = Some(index);
+                    commas.clear();
+                }
+                depth += 1;
+            }
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 && commas.is_empty() {
+                    // Kept as is, but groups nested in it and the ones after it are expanded
+                    let open = open.expect("set when depth was 0");
+                    let prefix = &pattern[..open];
+                    let suffixes = expand_braces(&pattern[index + 1..])?;
+                    let mut expanded = Vec::new();
+                    for inner in expand_braces(&pattern[open + 1..index])? {
+                        for suffix in &suffixes {
+                            expanded.push(format!("{}{{{}}}{}", prefix, inner, suffix));
+                        }
+                    }
+                    return Ok(expanded);
+                }
+                if depth == 0 {
+                    let open = open.expect("set when depth was 0");
+                    let (prefix, suffix) = (&pattern[..open], &pattern[index + 1..]);
+                    let mut bounds = vec![open];
+                    bounds.extend(&commas);
+                    bounds.push(index);
+                    let mut expanded = Vec::new();
+                    for bounds in bounds.windows(2) {
+                        let alternative = &pattern[bounds[0] + 1..bounds[1]];
+                        // The alternative and the rest are expanded as a whole, so later groups
+                        // are expanded as well
+                        expanded.extend(expand_braces(&format!(
+                            "{}{}{}",
+                            prefix, alternative, suffix
+                        ))?);
+                    }
+                    return Ok(expanded);
+                }
+            }
+            ',' if depth == 1 => commas.push(index),
+            _ => {}
+        }
+    }
+    if depth > 0 {
+        bail!("Unclosed `{{` in pattern `{}`", pattern);
+    }
+    Ok(vec![pattern.to_string()])
+}
+
+/// The supported config files in `config_dir` and its subdirectories with their paths relative
+/// to it (like `lang/rust.yaml`), in processing order.
+pub fn config_files(config_dir: &Path) -> Result<Vec<(PathBuf, String)>> {
+    let mut files = Vec::new();
+    let mut visited = HashSet::new();
+    collect_config_files(config_dir, config_dir, &mut visited, &mut files)?;
+    // Fragments are only used through the files extending them, see [`extends`]
+    let extended = extends::extended(&files);
+    files.retain(|(path, _)| {
+        let fragment = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('_'));
+        !fragment
+            || path
+                .canonicalize()
+                .map_or(true, |path| !extended.contains(&path))
+    });
+    // Later files take precedence, so e.g. `99-override.yaml` can override `00-base.yaml`
+    files.sort_by(|(_, a), (_, b)| a.cmp(b));
+    Ok(files)
+}
+
+/// Adds the config files below `dir` to `files`, `visited` holds the canonical paths of the
+/// directories already walked, so symlink loops are only followed once.
+fn collect_config_files(
+    config_dir: &Path,
+    dir: &Path,
+    visited: &mut HashSet<PathBuf>,
+    files: &mut Vec<(PathBuf, String)>,
+) -> Result<()> {
+    let read_error = || Error::Io {
+        path: dir.to_owned(),
+        message: format!("Failed to read config dir: {}", dir.display()),
+    };
+    let canonical = dir.canonicalize().with_context(read_error)?;
+    if !visited.insert(canonical) {
+        return Ok(());
+    }
+    for config_file in read_dir(dir).with_context(read_error)? {
+        let config_file = config_file?.path();
+        if config_file.is_dir() {
+            collect_config_files(config_dir, &config_file, visited, files)?;
+        } else if let Some(extension) = config_file.extension() {
+            let extension = extension.to_string_lossy().to_lowercase();
+            if ["yaml", "yml", "json", "toml"].contains(&extension.as_str()) {
+                let relative = config_file
+                    .strip_prefix(config_dir)
+                    .expect("Config files are below the config dir");
+                let filename = utf8_file_name(relative, &config_file)?;
+                files.push((config_file, filename));
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Config {
+    /// The file name of the config.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The file types this config generates `ftplugin` files for, from the file type flag of
+    /// `keys` and `abbreviations` and the `file_type` section.
+    pub fn file_types(&self) -> BTreeSet<&str> {
+        self.keys
+            .keys()
+            .filter_map(|flags| flags.file_type.as_deref())
+            .chain(
+                self.abbreviations
+                    .keys()
+                    .filter_map(|flags| flags.file_type.as_deref()),
+            )
+            .chain(self.file_type.keys().map(String::as_str))
+            .collect()
+    }
+}
+
+/// The name of the config at `path`, i.e. `filename` with `/` as separator. It ends up in the
+/// generated files and augroup names, so it is rejected instead of lossily converted if it is
+/// not valid UTF-8.
+fn utf8_file_name(filename: &Path, path: &Path) -> Result<String> {
+    let components: Option<Vec<_>> = filename
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect();
+    match components {
+        Some(components) => Ok(components.join("/")),
+        None => bail!("Config file name is not valid UTF-8: {}", path.display()),
+    }
+}
+
+/// Parses a single YAML, TOML or JSON config file, choosing the format by its extension. YAML
+/// files with several documents are only supported by [`parse_dir`].
+pub fn parse_config(path: &Path) -> Result<Config> {
+    let filename = match path.file_name() {
+        Some(filename) => utf8_file_name(Path::new(filename), path)?,
+        None => String::new(),
+    };
+    let mut configs = parse_named_config(path, filename.clone())?;
+    if configs.len() != 1 {
+        bail!(Error::Parse {
+            message: format!(
+                "{} contains {} YAML documents, only one is supported here",
+                filename,
+                configs.len()
+            ),
+            file: filename,
+            location: None,
+        });
+    }
+    Ok(configs.remove(0))
+}
+
+/// Parses the config at `path`, naming it `filename` in the generated files and errors.
+fn parse_named_config(path: &Path, filename: String) -> Result<Vec<Config>> {
+    parse_source(path, &filename)?
+        .into_iter()
+        .map(|(name, config)| {
+            read_sources(config, path.parent().unwrap_or_else(|| Path::new("")), name)
+        })
+        .collect()
+}
+
+/// Parses a config `source` that is not a file, like one read from stdin, in `format` (`yaml`,
+/// `json` or `toml`) and names it `name`. Files in `source` are relative to the current
+/// directory and `extends` is not supported, as there is no file to resolve it against.
+pub fn parse_str(source: &str, format: &str, name: &str) -> Result<Config> {
+    let config = parse_text(None, Some(&format.to_lowercase()), source.to_string(), name)?;
+    read_sources(config, Path::new(""), name.to_string())
+}
+
+/// Reads the files of `source` relative to `config_dir` and names the config `filename`.
+fn read_sources(mut config: Config, config_dir: &Path, filename: String) -> Result<Config> {
+    for source in &mut config.source {
+        let path = config_dir.join(&source.path);
+        source.content = fs::read_to_string(&path)
+            .map(unix_line_endings)
+            .with_context(|| Error::Io {
+                message: format!(
+                    "Failed to read sourced file: {} in {}",
+                    path.display(),
+                    filename
+                ),
+                path: path.clone(),
+            })?;
+    }
+    config.name = filename;
+    Ok(config)
+}
+
+/// Parses the supported config files in `config_dir`, in the order they are applied.
+pub fn parse_dir(config_dir: &Path) -> Result<Vec<Config>> {
+    Ok(parse_files(config_files(config_dir)?)?
+        .into_iter()
+        .flat_map(|(_, configs, _)| configs)
+        .collect())
+}
+
+/// Parses `files` as returned by [`config_files`] into the configs of each file (one per YAML
+/// document), with its name and the time it took to parse.
+pub fn parse_files(files: Vec<(PathBuf, String)>) -> Result<Vec<(String, Vec<Config>, Duration)>> {
+    parse_configs(files)
+        .into_iter()
+        .map(|(filename, configs, duration)| Ok((filename, configs?, duration)))
+        .collect()
+}
+
+fn parse_source(path: &Path, filename: &str) -> Result<Vec<(String, Config)>> {
+    let source = fs::read_to_string(path).with_context(|| Error::Io {
+        path: path.to_owned(),
+        message: format!("Failed to read file: {}", path.display()),
+    })?;
+    let format = format_of(path);
+    let mut documents = match format.as_deref() {
+        Some("yaml" | "yml") => yaml_documents(&unix_line_endings(source.clone())),
+        _ => Vec::new(),
+    };
+    if documents.len() <= 1 {
+        let source = documents.pop().unwrap_or(source);
+        let config = parse_text(Some(path), format.as_deref(), source, filename)?;
+        return Ok(vec![(filename.to_string(), config)]);
+    }
+    documents
+        .into_iter()
+        .enumerate()
+        .map(|(index, document)| {
+            let name = format!("{}#{}", filename, index + 1);
+            let config = parse_text(Some(path), format.as_deref(), document, &name)?;
+            Ok((name, config))
+        })
+        .collect()
+}
+
+/// The documents of a YAML file, separated by lines starting with `---`. Each is padded to keep
+/// the line and column numbers of the file for error messages, and documents without any
+/// content, like the one before a leading `---`, are dropped.
+fn yaml_documents(source: &str) -> Vec<String> {
+    let mut documents = Vec::new();
+    let mut document = String::new();
+    for (lines, line) in source.split_inclusive('\n').enumerate() {
+        let separator = line
+            .strip_prefix("---")
+            .filter(|rest| rest.trim().is_empty() || rest.starts_with(char::is_whitespace));
+        match separator {
+            Some(rest) => {
+                documents.push(document);
+                document = format!("{}   {}", "\n".repeat(lines), rest);
+            }
+            None => document.push_str(line),
+        }
+    }
+    documents.push(document);
+    documents
+        .into_iter()
+        .filter(|document| {
+            document.lines().any(|line| {
+                let line = line.trim();
+                !line.is_empty() && !line.starts_with('#')
+            })
+        })
+        .collect()
+}
+
+/// Parses the config `source` in `format` (the lowercase extension, see [`format_of`]), read from
+/// `path` if it is a file, which `extends` is resolved against.
+fn parse_text(
+    path: Option<&Path>,
+    format: Option<&str>,
+    source: String,
+    filename: &str,
+) -> Result<Config> {
+    let source = unix_line_endings(source);
+    let merged = match path {
+        Some(path) => extends::resolve(path, &source, filename)?,
+        None => None,
+    };
+    let config: Config = match merged {
+        Some(merged) => serde_yaml::from_value(merged)
+            .map_err(|error| parse_error(error, None, filename, &source))?,
+        None => deserialize(format, &source, filename)?,
+    };
+    let mut config = Config {
+        text: source,
+        format: format.map(str::to_string),
+        ..config
+    };
+    config.resolve_flags(filename)?;
+    Ok(config)
+}
+
+impl Config {
+    /// Parses the flags of `abbreviations`, `keys` and the mappings of `auto_commands`, applying
+    /// the `default_map_flags` to the mappings, and merges the mappings of differently written but equivalent flags
+    /// (e.g. `nv` and `vn`) instead of keeping only the last ones.
+    fn resolve_flags(&mut self, filename: &str) -> Result<()> {
+        const KEY: &str = "default_map_flags";
+        let default = match &self.default_map_flags {
+            None => BitFlags::empty(),
+            Some(flags) => {
+                let error = |message: String| Error::Flag {
+                    file: filename.to_string(),
+                    flag: flags.clone(),
+                    message: format!("Invalid `{}` in {}: {}", KEY, filename, message),
+                    location: key_location(&self.text, KEY, flags),
+                };
+                let default = MapFlags::parse_letters(flags)
+                    .and_then(|default| default.check(flags).map(|_| default))
+                    .map_err(|message| error(message.to_string()))?;
+                if default.label.is_some() || default.file_type.is_some() {
+                    bail!(error(
+                        "only mode and modifier flags are supported, no label or file type"
+                            .to_string()
+                    ));
+                }
+                default.flags
+            }
+        };
+
+        for (source, abbreviations) in std::mem::take(&mut self.raw_abbreviations) {
+            let flags = AbbreviationFlags::parse(&source).map_err(|error| {
+                flag_error(error, &source, "abbreviations", filename, &self.text)
+            })?;
+            self.abbreviations.insert(flags, (source, abbreviations));
+        }
+
+        // flags -> (first way they were written, mappings)
+        let mut keys: BTreeMap<MapFlags, (String, MappingGroup)> = BTreeMap::new();
+        for (source, group) in std::mem::take(&mut self.raw_keys) {
+            let error =
+                |error: anyhow::Error| flag_error(error, &source, "keys", filename, &self.text);
+            let flags = MapFlags::parse(&source, default).map_err(error)?;
+            match keys.get_mut(&flags) {
+                None => {
+                    let written_flags = group
+                        .mappings
+                        .keys()
+                        .map(|lhs| (lhs.clone(), source.clone()))
+                        .collect();
+                    let group = MappingGroup {
+                        written_flags,
+                        ..group
+                    };
+                    keys.insert(flags, (source, group));
+                }
+                Some((first, existing)) => {
+                    match (&existing.requires_plugin, group.requires_plugin) {
+                        (Some(required), Some(plugin)) if *required != plugin => {
+                            return Err(error(anyhow!(
+                                "`{}` and the equivalent `{}` require different plugins: `{}` \
+                                 and `{}`",
+                                first,
+                                source,
+                                required,
+                                plugin
+                            )));
+                        }
+                        (_, Some(plugin)) => existing.requires_plugin = Some(plugin),
+                        (_, None) => {}
+                    }
+                    for (lhs, mapping) in group.mappings {
+                        if existing.mappings.contains_key(&lhs) {
+                            return Err(error(anyhow!(
+                                "Mapping `{}` is defined for both `{}` and the equivalent `{}`",
+                                lhs,
+                                first,
+                                source
+                            )));
+                        }
+                        existing.written_flags.insert(lhs.clone(), source.clone());
+                        existing.mappings.insert(lhs, mapping);
+                    }
+                }
+            }
+        }
+        self.keys = keys
+            .into_iter()
+            .map(|(flags, (_, group))| (flags, group))
+            .collect();
+
+        let text = &self.text;
+        for auto_command in &mut self.auto_commands {
+            for cmd in &mut auto_command.cmd {
+                if let AutoCommandCmd::Map(mapping) = cmd {
+                    mapping.flags = MapFlags::parse(&mapping.map, default).map_err(|error| {
+                        flag_error(error, &mapping.map, "auto_commands", filename, text)
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where `key` is first written in `text` after the start of `section`, e.g. the flags of a
+/// mapping group. Flags are parsed after deserializing, which has no locations of its own.
+fn key_location(text: &str, section: &str, key: &str) -> Option<(usize, usize)> {
+    let is_token = |line: &str, start: usize| {
+        let before = line[..start].chars().next_back();
+        let after = line[start + key.len()..].chars().next();
+        matches!(
+            before,
+            None | Some(' ' | '\t' | '"' | '\'' | '.' | '{' | ',')
+        ) && matches!(
+            after,
+            None | Some(' ' | '\t' | '"' | '\'' | ':' | ']' | '=' | ',' | '}')
+        )
+    };
+    let find = |line: &str, from: usize| {
+        line[from..]
+            .match_indices(key)
+            .map(|(start, _)| from + start)
+            .find(|&start| is_token(line, start))
+    };
+    let mut lines = text.lines().enumerate();
+    let (index, line, from) = lines.find_map(|(index, line)| {
+        let start = line.find(section)?;
+        Some((index, line, start + section.len()))
+    })?;
+    let (index, line, start) = std::iter::once((index, line, from))
+        .chain(lines.map(|(index, line)| (index, line, 0)))
+        .find_map(|(index, line, from)| Some((index, line, find(line, from)?)))?;
+    Some((index + 1, line[..start].chars().count() + 1))
+}
+
+/// A [`Error::Flag`] for the invalid `flags` in `section` of `filename`, caused by `error`.
+fn flag_error(
+    error: anyhow::Error,
+    flags: &str,
+    section: &str,
+    filename: &str,
+    source: &str,
+) -> anyhow::Error {
+    let location = key_location(source, section, flags);
+    anyhow!("{}: {:#}", section, error).context(Error::Flag {
+        file: filename.to_string(),
+        flag: flags.to_string(),
+        message: parse_message(location, filename, source),
+        location,
+    })
+}
+
+/// `text` with CRLF line endings replaced by LF, so files edited on Windows do not end up with
+/// mixed line endings in the generated files.
+pub(crate) fn unix_line_endings(text: String) -> String {
+    if text.contains('\r') {
+        text.replace("\r\n", "\n")
+    } else {
+        text
+    }
+}
+
+/// The format of the config file at `path`, its lowercase extension.
+fn format_of(path: &Path) -> Option<String> {
+    path.extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+}
+
+/// Deserializes YAML, JSON or TOML by its `format`, see [`format_of`].
+fn deserialize<T: DeserializeOwned>(
+    format: Option<&str>,
+    source: &str,
+    filename: &str,
+) -> Result<T> {
+    match format {
+        Some("json") => {
+            let value = json::parse(source).map_err(|error| {
+                let location = Some(error.location());
+                parse_error(error, location, filename, source)
+            })?;
+            serde_yaml::from_value(value)
+                .map_err(|error| parse_error(error, None, filename, source))
+        }
+        Some("yaml" | "yml") => serde_yaml::from_str(source).map_err(|error| {
+            let location = error
+                .location()
+                .map(|location| (location.line(), location.column()));
+            parse_error(error, location, filename, source)
+        }),
+        Some("toml") => toml::from_str(source).map_err(|error| {
+            // toml locations are 0-based
+            let location = error
+                .line_col()
+                .map(|(line, column)| (line + 1, column + 1));
+            parse_error(error, location, filename, source)
+        }),
+        _ => bail!(Error::Parse {
+            file: filename.to_string(),
+            message: format!("Unsupported config file: {}", filename),
+            location: None,
+        }),
+    }
+}
+
+/// Reads and parses `files` in parallel, one thread per file, keeping their order.
+fn parse_configs(files: Vec<(PathBuf, String)>) -> Vec<(String, Result<Vec<Config>>, Duration)> {
+    thread::scope(|scope| {
+        let handles: Vec<_> = files
+            .into_iter()
+            .map(|(path, filename)| {
+                scope.spawn(move || {
+                    let start = Instant::now();
+                    let config = parse_named_config(&path, filename.clone());
+                    (filename, config, start.elapsed())
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Parsing should not panic"))
+            .collect()
+    })
+}
+
+/// Checks a parsed config for errors that the deserialization cannot catch, returning all of
+/// them.
+fn validate(config: &Config, allow_unknown_events: bool) -> Vec<anyhow::Error> {
+    let filename = &config.name;
+    let mut errors = Vec::new();
+    for AutoCommandMapping { flags, lhs, .. } in config
+        .auto_commands
+        .iter()
+        .flat_map(|auto_command| &auto_command.cmd)
+        .filter_map(|cmd| match cmd {
+            AutoCommandCmd::Map(mapping) => Some(mapping),
+            AutoCommandCmd::Cmd(_) => None,
+        })
+    {
+        if flags.file_type.is_some() {
+            errors.push(validation_error(
+                filename,
+                format!(
+                    "Mapping `{}` of an autocommand in {} cannot use the file type flag (`f`), \
+                     trigger it on `FileType` instead",
+                    lhs, filename
+                ),
+            ));
+        }
+    }
+    if config
+        .auto_commands
+        .iter()
+        .any(|auto_command| auto_command.triggers.is_empty())
+    {
+        errors.push(validation_error(
+            filename,
+            format!("Autocommand in {} has no `triggers`", filename),
+        ));
+    }
+    for trigger in config
+        .auto_commands
+        .iter()
+        .flat_map(|auto_command| &auto_command.triggers)
+    {
+        if !events::is_known(trigger) {
+            let message = format!("Unknown autocommand event `{}` in {}", trigger, filename);
+            if allow_unknown_events {
+                eprintln!("Warning: {}", message);
+            } else {
+                errors.push(validation_error(
+                    filename,
+                    format!(
+                        "{} (use `User` for custom events or pass --allow-unknown-events)",
+                        message
+                    ),
+                ));
+            }
+        }
+    }
+    for (name, command) in &config.commands {
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            errors.push(validation_error(
+                filename,
+                format!(
+                    "User command `{}` in {} must start with an uppercase letter",
+                    name, filename
+                ),
+            ));
+        }
+        if command.rhs.is_some() == command.lua.is_some() {
+            errors.push(validation_error(
+                filename,
+                format!(
+                    "User command `{}` in {} needs exactly one of `rhs` and `lua`",
+                    name, filename
+                ),
+            ));
+        }
+        let given = |attribute: &Option<CommandAttribute>| {
+            !matches!(attribute, None | Some(CommandAttribute::Bool(false)))
+        };
+        if given(&command.range) && given(&command.count) {
+            errors.push(validation_error(
+                filename,
+                format!(
+                    "User command `{}` in {} can either take a `range` or a `count`, not both",
+                    name, filename
+                ),
+            ));
+        }
+        if let Some(addr) = &command.addr {
+            if !ADDRESS_TYPES.contains(&addr.as_str()) {
+                errors.push(validation_error(
+                    filename,
+                    format!(
+                        "Unsupported `addr` `{}` of user command `{}` in {} (supported: {})",
+                        addr,
+                        name,
+                        filename,
+                        ADDRESS_TYPES.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+    for (flags, (_, abbreviations)) in &config.abbreviations {
+        for (lhs, abbreviation) in abbreviations {
+            let command_start = matches!(
+                abbreviation,
+                Abbreviation::Guarded {
+                    expr: AbbreviationGuard::CommandStart(true),
+                    ..
+                }
+            );
+            if command_start && flags.insert {
+                errors.push(validation_error(
+                    filename,
+                    format!(
+                        "Abbreviation `{}` in {} is only guarded for the command line (`expr: true`), \
+                         but also defined in insert mode (give the guard as an expression instead)",
+                        lhs, filename
+                    ),
+                ));
+            }
+        }
+    }
+    for (
+        flags,
+        MappingGroup {
+            requires_plugin,
+            mappings,
+            ..
+        },
+    ) in &config.keys
+    {
+        if requires_plugin
+            .as_deref()
+            .is_some_and(|plugin| plugin.trim().is_empty())
+        {
+            errors.push(validation_error(
+                filename,
+                format!("`requires_plugin` of mappings in {} is empty", filename),
+            ));
+        }
+        let modes = map_modes(flags.flags);
+        for FlatBinding { lhs: key, rhs, .. } in flat_bindings(mappings) {
+            let disabled = match &rhs {
+                Rhs::Nop => true,
+                Rhs::Shared(rhs) => rhs.is_empty(),
+                Rhs::PerMode(_) => false,
+            };
+            if flags.flags.contains(MapFlag::Disable) && !disabled {
+                errors.push(validation_error(
+                    filename,
+                    format!(
+                        "Mapping `{}` in {} is disabled (`x`), its rhs has to be empty or null",
+                        key, filename
+                    ),
+                ));
+            }
+            if flags.flags.contains(MapFlag::Expr) && rhs.is_lua() {
+                errors.push(validation_error(
+                    filename,
+                    format!(
+                        "Mapping `{}` in {} is an expression (`e`), so it cannot call a lua function",
+                        key, filename
+                    ),
+                ));
+            }
+            if let Err(error) = rhs.per_mode(&modes) {
+                errors.push(error.context(Error::Validation {
+                    file: filename.clone(),
+                    message: format!("In mapping `{}` in {}", key, filename),
+                }));
+            }
+        }
+    }
+    let file_types = config
+        .file_types()
+        .into_iter()
+        .chain(
+            config
+                .auto_commands
+                .iter()
+                .filter_map(|auto_command| auto_command.file_type.as_deref()),
+        )
+        .collect::<BTreeSet<_>>();
+    for file_type in file_types {
+        // Nvim loads `ftplugin/<ft>_*.<ext>` for every part of a compound file type like
+        // `c.doxygen`, but never for the whole one
+        let valid = file_type
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if file_type.is_empty() || !valid {
+            errors.push(validation_error(filename, format!(
+                "Invalid file type `{}` in {}, it is used as the name of `ftplugin/<file type>_config` \
+                 (supported characters: a-z, A-Z, 0-9, _ and -)",
+                file_type,
+                filename
+            )));
+        }
+    }
+    if let Some(output) = &config.output {
+        let valid = output
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if output.is_empty() || !valid || output == "config" {
+            errors.push(validation_error(
+                filename,
+                format!(
+                    "Invalid output `{}` in {}, expected a file name without extension other than \
+                 `config` (supported characters: a-z, A-Z, 0-9, _ and -)",
+                    output, filename
+                ),
+            ));
+        }
+    }
+    errors.extend(validate_let(&config.r#let, filename));
+    for name in &config.unlet {
+        if let Some((scope, _)) = name.split_once(':') {
+            if scope.len() != 1 || variable_scope(scope).is_none() {
+                errors.push(validation_error(filename, format!(
+                    "Unsupported scope `{}:` of unlet variable `{}` in {} (supported: g:, b:, w:, t:)",
+                    scope,
+                    name,
+                    filename
+                )));
+            }
+        }
+    }
+    errors.extend(validate_set_value(&config.set_value, filename));
+    errors.extend(validate_set_conflicts(
+        &config.set,
+        &config.set_value,
+        filename,
+    ));
+    for options in config.file_type.values() {
+        errors.extend(validate_let(&options.r#let, filename));
+        errors.extend(validate_set_value(&options.set_value, filename));
+        errors.extend(validate_set_conflicts(
+            &options.set,
+            &options.set_value,
+            filename,
+        ));
+    }
+    for (name, highlight) in &config.highlights {
+        let Highlight {
+            fg,
+            bg,
+            bold,
+            italic,
+            underline,
+            link,
+        } = highlight;
+        if link.is_some() && (fg.is_some() || bg.is_some() || *bold || *italic || *underline) {
+            errors.push(validation_error(filename, format!(
+                "Highlight `{}` in {} can either `link` to another group or set attributes, not both",
+                name,
+                filename
+            )));
+        }
+    }
+    errors
+}
+
+/// Checks every config file in `config_dir`, returning all errors of every file with errors
+/// instead of stopping at the first one.
+pub fn validate_all(
+    config_dir: &Path,
+    allow_unknown_events: bool,
+) -> Result<Vec<(String, Vec<anyhow::Error>)>> {
+    Ok(parse_configs(config_files(config_dir)?)
+        .into_iter()
+        .map(|(filename, configs, _)| {
+            let errors = match configs {
+                Ok(configs) => configs
+                    .iter()
+                    .flat_map(|config| validate(config, allow_unknown_events))
+                    .collect(),
+                Err(error) => vec![error],
+            };
+            (filename, errors)
+        })
+        .filter(|(_, errors)| !errors.is_empty())
+        .collect())
+}
+
+/// The lines of the generated files, see [`GeneratedFiles`].
+struct Output {
+    files: BTreeMap<(Backend, Option<String>), Vec<String>>,
+    plugin_files: BTreeMap<(Backend, String), Vec<String>>,
+    /// See [`Options::split`].
+    split: bool,
+}
+
+/// A file of [`Output`].
+enum Target {
+    File(Option<String>),
+    Plugin(String),
+}
+
+impl Output {
+    /// The file for `section` (`config` or one of [`SPLIT_SECTIONS`]) of `file_type`. Global
+    /// lines go to `output` if given, otherwise to their section if split.
+    fn target(&self, file_type: &Option<String>, section: &str, output: Option<&str>) -> Target {
+        match (file_type, output) {
+            (None, Some(output)) => Target::Plugin(output.to_string()),
+            (None, None) if self.split && section != "config" => {
+                Target::Plugin(section.to_string())
+            }
+            _ => Target::File(file_type.clone()),
+        }
+    }
+
+    /// The lines of the `backend` file for `section` of `file_type`, see [`Output::target`].
+    fn lines(
+        &mut self,
+        backend: Backend,
+        file_type: &Option<String>,
+        section: &str,
+        output: Option<&str>,
+    ) -> &mut Vec<String> {
+        match self.target(file_type, section, output) {
+            Target::File(file_type) => self.files.entry((backend, file_type)).or_default(),
+            Target::Plugin(name) => self.plugin_files.entry((backend, name)).or_default(),
+        }
+    }
+
+    /// The path of the file for `section` of `file_type` relative to the output dir, see
+    /// [`Output::target`].
+    fn path(
+        &self,
+        backend: Backend,
+        file_type: &Option<String>,
+        section: &str,
+        output: Option<&str>,
+    ) -> String {
+        let extension = backend.extension();
+        match self.target(file_type, section, output) {
+            Target::File(None) => format!("plugin/config.{}", extension),
+            Target::File(Some(file_type)) => {
+                format!("ftplugin/{}_config.{}", file_type, extension)
+            }
+            Target::Plugin(name) => format!("plugin/{}.{}", name, extension),
+        }
+    }
+}
+
+/// The comment of [`Options::annotate`]. It gets its own line, as some commands would take a
+/// trailing comment as part of their argument.
+fn annotation(backend: Backend, filename: &str, modes: &[char]) -> String {
+    let modes: Vec<_> = modes.iter().map(char::to_string).collect();
+    backend.comment(&format!(
+        "From {} in mode{} {}",
+        filename,
+        if modes.len() == 1 { "" } else { "s" },
+        modes.join(", ")
+    ))
+}
+
+/// Generates the files from `configs`, later configs can override earlier ones.
+pub fn generate(configs: &[Config], options: &Options) -> Result<GeneratedFiles> {
+    let &Options {
+        backend,
+        strict,
+        allow_unknown_events,
+        expand_env,
+        split,
+        manifest,
+        format,
+        group_leader,
+        annotate,
+    } = options;
+
+    for config in configs {
+        if let Some(error) = validate(config, allow_unknown_events).into_iter().next() {
+            return Err(error);
+        }
+    }
+
+    let mut out = Output {
+        // The global file is always generated, so an empty config dir results in an empty config
+        files: BTreeMap::from([((backend, None), Vec::new())]),
+        plugin_files: if split {
+            SPLIT_SECTIONS
+                .iter()
+                .map(|section| ((backend, section.to_string()), Vec::new()))
+                .collect()
+        } else {
+            BTreeMap::new()
+        },
+        split,
+    };
+
+    let expand = |text: String| {
+        if expand_env {
+            env::expand(&text)
+        } else {
+            Ok(text)
+        }
+    };
+    let expand_value = |value: Value| {
+        if expand_env {
+            env::expand_value(value)
+        } else {
+            Ok(value)
+        }
+    };
+
+    let mut plugins = Vec::new();
+    let mut cleared_groups = HashSet::new();
+    let shared_groups: BTreeSet<&str> = configs
+        .iter()
+        .flat_map(|config| &config.augroups)
+        .map(String::as_str)
+        .collect();
+    // Autocommands of the groups in `augroups`, with the last file that added to them
+    let mut shared_autocmds: BTreeMap<String, (Option<&str>, Vec<String>)> = BTreeMap::new();
+    let default_backend = backend;
+    // The leader variables with the conditions of the file setting them, see `Config::leader`
+    let mut leaders: Vec<(&str, &str, &[Condition])> = Vec::new();
+    let leader_target = out.path(default_backend, &None, "config", None);
+    let mut autocmd_count = 0;
+    let mut mappings = DefinedMappings::new();
+    let mut entries = Vec::new();
+    let mut counts = BTreeMap::new();
+    for config in configs {
+        let filename = &config.name;
+        let output = config.output.as_deref();
+        let backend = config.backend.unwrap_or(backend);
+        let lines = if manifest {
+            Lines::of(config.format.as_deref(), &config.text)
+        } else {
+            Lines::default()
+        };
+        // Counts an entry and adds it to the manifest
+        let mut record = |kind, name: &str, mode, target: &str, line: Option<usize>| {
+            *counts.entry(kind).or_default() += 1;
+            if manifest {
+                entries.push(manifest::Entry {
+                    kind,
+                    name: name.to_string(),
+                    mode,
+                    source: filename.clone(),
+                    line,
+                    output: target.to_string(),
+                });
+            }
+        };
+        let guards = {
+            let vimscript = out.lines(backend, &None, "config", output);
+            vimscript.push(format!(
+                "\n\n{}",
+                backend.comment(&format!("File: {}", filename))
+            ));
+            if let Some(description) = &config.description {
+                vimscript.push(backend.comment(description));
+            }
+            let Some(guards) = runtime_checks(backend, &config.when) else {
+                let conditions: Vec<_> = config.when.iter().map(Condition::to_string).collect();
+                vimscript.push(backend.comment(&format!(
+                    "Skipped, `{}` does not hold",
+                    conditions.join("`, `")
+                )));
+                continue;
+            };
+            if !split || output.is_some() {
+                vimscript.push(format!("\n{}", backend.comment("Keybindings:")));
+            }
+            guards
+        };
+        for (variable, key, value) in [
+            ("g:mapleader", "leader", &config.leader),
+            ("g:maplocalleader", "localleader", &config.localleader),
+        ] {
+            if let Some(value) = value {
+                record(
+                    "variable",
+                    variable,
+                    None,
+                    &leader_target,
+                    lines.key(&[key]),
+                );
+                let guards = runtime_checks(default_backend, &config.when).unwrap_or_default();
+                // Files setting it only under runtime conditions override the earlier value then
+                if guards.is_empty() {
+                    leaders.retain(|(set, ..)| *set != variable);
+                }
+                leaders.push((variable, value, &config.when));
+            }
+        }
+        // Where the output of this file starts, to wrap it in the guards
+        let starts: BTreeMap<_, _> = out
+            .files
+            .iter()
+            .map(|(file, lines)| (file.clone(), lines.len()))
+            .collect();
+        let plugin_starts: BTreeMap<_, _> = out
+            .plugin_files
+            .iter()
+            .map(|(file, lines)| (file.clone(), lines.len()))
+            .collect();
+        for (
+            MapFlags {
+                flags,
+                label,
+                file_type,
+            },
+            MappingGroup {
+                requires_plugin,
+                mappings: k,
+                written_flags,
+            },
+        ) in &config.keys
+        {
+            let target = out.path(backend, file_type, "keys", output);
+            let vimscript = out.lines(backend, file_type, "keys", output);
+            if let Some(label) = label {
+                vimscript.push(backend.comment(label));
+            }
+            // Where the mappings start, to guard them by `requires_plugin`
+            let start = vimscript.len();
+            let mut kbs = flat_bindings(k);
+            let group_leader = group_leader && flags.contains(MapFlag::Leader);
+            if group_leader {
+                kbs.sort_by(|a, b| first_key(&lhs(&a.lhs)).cmp(first_key(&lhs(&b.lhs))));
+            }
+            let mut leader_group = None;
+            let modes = map_modes(*flags);
+            let full_lhs = |raw_key: &str| leader_lhs(*flags, raw_key);
+            let options = MapOptions {
+                desc: label.clone(),
+                ..map_options(*flags)
+            };
+            for FlatBinding {
+                lhs: raw_key,
+                path,
+                rhs,
+                label: binding_label,
+                group_labels,
+                silent,
+                opts,
+            } in kbs
+            {
+                let key = full_lhs(&raw_key);
+                let line = written_flags.get(&path[0]).and_then(|flags| {
+                    let mut keys = vec!["keys", flags.as_str()];
+                    keys.extend(path.iter().map(String::as_str));
+                    lines.key(&keys)
+                });
+                if group_leader {
+                    let prefix = first_key(&lhs(&raw_key)).to_string();
+                    if leader_group.as_ref() != Some(&prefix) {
+                        vimscript.push(backend.comment(&format!("<LEADER>{}", prefix)));
+                        leader_group = Some(prefix);
+                    }
+                }
+                let mut options = options.clone();
+                if let Some(silent) = silent {
+                    options.silent = silent;
+                }
+                let opts = opts
+                    .iter()
+                    .map(|option| option.parse())
+                    .collect::<Result<Vec<MapOption>>>()
+                    .with_context(|| format!("In mapping `{}` in {}", key, filename))?;
+                for option in &opts {
+                    options.enable(*option);
+                }
+                let rhs = if flags.contains(MapFlag::Disable) {
+                    Rhs::Nop
+                } else {
+                    rhs
+                };
+                let wrap = flags.contains(MapFlag::CmdWrap) && rhs != Rhs::Nop;
+                let bindings = rhs
+                    .per_mode(&modes)
+                    .and_then(|bindings| {
+                        bindings
+                            .into_iter()
+                            .map(|(modes, binding)| {
+                                let plug = is_plug(&binding);
+                                let body = match binding.strip_prefix(LUA_RHS) {
+                                    Some(function) => {
+                                        Body::Lua(expand(function.trim().to_string())?)
+                                    }
+                                    None if wrap && !plug => {
+                                        Body::Cmd(expand(format!("<CMD>{}<CR>", binding))?)
+                                    }
+                                    None => Body::Cmd(expand(binding)?),
+                                };
+                                Ok((modes, body, plug))
+                            })
+                            .collect::<Result<Vec<_>>>()
+                    })
+                    .with_context(|| format!("In mapping `{}` in {}", key, filename))?;
+                for mode in &modes {
+                    record("mapping", &key, Some(*mode), &target, line);
+                    let mapping = (file_type.clone(), options.buffer, *mode, key.clone());
+                    let defined = (filename.clone(), options.nowait);
+                    if let Some((previous, _)) = mappings.insert(mapping, defined) {
+                        let message = format!(
+                            "Mapping `{}` in mode `{}` is defined in both {} and {}",
+                            key, mode, previous, filename
+                        );
+                        if strict {
+                            bail!(message);
+                        }
+                        eprintln!("Warning: {}", message);
+                    }
+                }
+                for label in group_labels {
+                    vimscript.push(backend.comment(&label));
+                }
+                // The group label describes the prefix, not the individual suffixes
+                if let Some(label) = binding_label {
+                    if let Some(label) = &label {
+                        vimscript.push(backend.comment(label));
+                    }
+                    options.desc = label;
+                }
+                for (modes, binding, plug) in bindings {
+                    if annotate {
+                        vimscript.push(annotation(backend, filename, &modes));
+                    }
+                    if !plug {
+                        vimscript.extend(backend.map(&modes, &key, binding, &options));
+                        continue;
+                    }
+                    if wrap {
+                        eprintln!(
+                            "Warning: Ignoring the command wrapping flag (`w` or `c`) for the \
+                             `<Plug>` mapping `{}` in {}",
+                            key, filename
+                        );
+                    }
+                    // `<Plug>` mappings only exist as mappings, so they need to be remapped
+                    let options = MapOptions {
+                        recursive: true,
+                        silent: silent.unwrap_or_else(|| opts.contains(&MapOption::Silent)),
+                        ..options.clone()
+                    };
+                    vimscript.extend(backend.map(&modes, &key, binding, &options));
+                }
+            }
+            let groups: Vec<_> = prefix_groups(k, label.as_ref())
+                .into_iter()
+                .map(|(prefix, label)| (full_lhs(&prefix), label))
+                .collect();
+            vimscript.extend(backend.which_key_groups(&groups, &modes, options.buffer));
+            if let Some(plugin) = requires_plugin {
+                let mappings = vimscript.split_off(start);
+                vimscript.extend(backend.guard(&[backend.plugin_available(plugin)], mappings));
+            }
+        }
+
+        let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let autocmds_target = out.path(backend, &None, "autocmds", output);
+        let shared_target = out.path(default_backend, &None, "autocmds", None);
+        // Shared groups are emitted after all files, so they need the guards of their file
+        let file_guards = runtime_checks(default_backend, &config.when).unwrap_or_default();
+        for (
+            index,
+            AutoCommand {
+                triggers,
+                cmd,
+                lua,
+                matching,
+                event,
+                silent,
+                file_type,
+                group,
+                once,
+                nested,
+                comment,
+                desc,
+                when,
+            },
+        ) in config.auto_commands.iter().enumerate()
+        {
+            let group = group.clone().unwrap_or_else(|| augroup_name(filename));
+            let shared = shared_groups.contains(group.as_str());
+            let backend = if shared { default_backend } else { backend };
+            let Some(mut autocmd_guards) = runtime_checks(backend, when) else {
+                continue;
+            };
+            if shared {
+                autocmd_guards.splice(0..0, file_guards.iter().cloned());
+            }
+            record(
+                "autocommand",
+                &triggers.join(","),
+                None,
+                if shared {
+                    &shared_target
+                } else {
+                    &autocmds_target
+                },
+                lines.item(&["auto_commands"], index, config.auto_commands.len()),
+            );
+            let options = AutoCommandOptions {
+                silent: *silent,
+                once: *once,
+                nested: *nested,
+                event,
+                desc: desc.as_deref(),
+            };
+            let matching = if !matching.is_empty() {
+                let mut expanded = Vec::new();
+                for pattern in matching {
+                    expanded.extend(expand_braces(pattern).with_context(|| {
+                        format!(
+                            "In autocommand for `{}` in {}",
+                            triggers.join(","),
+                            filename
+                        )
+                    })?);
+                }
+                expanded
+            } else if file_type.is_some() {
+                vec!["<buffer>".to_string()]
+            } else {
+                vec!["*".to_string()]
+            };
+
+            let cmd = cmd
+                .iter()
+                .map(|cmd| match cmd {
+                    AutoCommandCmd::Cmd(cmd) => Ok(vec![expand(cmd.clone())?]),
+                    AutoCommandCmd::Map(AutoCommandMapping {
+                        flags, lhs, rhs, ..
+                    }) => {
+                        let flags = flags.flags;
+                        let rhs = if flags.contains(MapFlag::Disable) {
+                            "<Nop>".to_string()
+                        } else {
+                            expand(rhs.clone())?
+                        };
+                        let plug = is_plug(&rhs);
+                        let body = match rhs.strip_prefix(LUA_RHS) {
+                            Some(function) => Body::Lua(function.trim().to_string()),
+                            None if flags.contains(MapFlag::CmdWrap) && !plug => {
+                                Body::Cmd(format!("<CMD>{}<CR>", rhs))
+                            }
+                            None => Body::Cmd(rhs),
+                        };
+                        let lhs = leader_lhs(flags, lhs);
+                        // The autocommand runs them as ex commands, regardless of the backend
+                        let options = MapOptions {
+                            recursive: flags.contains(MapFlag::Recursive) || plug,
+                            ..map_options(flags)
+                        };
+                        Ok(Backend::Vim.map(&map_modes(flags), &lhs, body, &options))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(|cmd| cmd.concat())
+                .with_context(|| {
+                    format!(
+                        "In autocommand for `{}` in {}",
+                        triggers.join(","),
+                        filename
+                    )
+                })?;
+            for body in cmd
+                .into_iter()
+                .map(Body::Cmd)
+                .chain(lua.iter().map(|lua| Body::Lua(lua.trim_end().to_string())))
+            {
+                let autocmd =
+                    backend.autocmd(autocmd_count, &group, triggers, &matching, body, &options);
+                autocmd_count += 1;
+                let autocmds = if shared {
+                    let (last_file, autocmds) = shared_autocmds.entry(group.clone()).or_default();
+                    if *last_file != Some(filename.as_str()) {
+                        autocmds.push(backend.comment(&format!("From {}", filename)));
+                        *last_file = Some(filename);
+                    }
+                    autocmds
+                } else {
+                    groups.entry(group.clone()).or_default()
+                };
+                if let Some(comment) = comment {
+                    autocmds.push(backend.comment(comment));
+                }
+                if autocmd_guards.is_empty() {
+                    autocmds.push(autocmd);
+                } else {
+                    autocmds.extend(backend.guard(&autocmd_guards, vec![autocmd]));
+                }
+            }
+        }
+        {
+            let vimscript = out.lines(backend, &None, "autocmds", output);
+            for (group, autocmds) in groups {
+                // Groups shared between files must only be cleared once
+                let clear = cleared_groups.insert((backend, group.clone()));
+                vimscript.extend(backend.augroup(&group, clear));
+                vimscript.extend(autocmds);
+                vimscript.extend(backend.augroup_end());
+            }
+        }
+
+        for (
+            AbbreviationFlags {
+                insert,
+                command,
+                recursive,
+                buffer,
+                file_type,
+            },
+            (written_flags, abbreviations),
+        ) in &config.abbreviations
+        {
+            let target = out.path(backend, file_type, "keys", output);
+            let vimscript = out.lines(backend, file_type, "keys", output);
+            let modes: Vec<char> = [(*insert, 'i'), (*command, 'c')]
+                .iter()
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, mode)| *mode)
+                .collect();
+            for (lhs, abbreviation) in abbreviations {
+                for mode in &modes {
+                    let line = lines.key(&["abbreviations", written_flags, lhs]);
+                    record("abbreviation", lhs, Some(*mode), &target, line);
+                }
+                if annotate {
+                    vimscript.push(annotation(backend, filename, &modes));
+                }
+                let (rhs, expr) = abbreviation.rhs(lhs);
+                let options = MapOptions {
+                    recursive: *recursive,
+                    buffer: *buffer,
+                    expr,
+                    ..MapOptions::default()
+                };
+                vimscript.extend(backend.abbreviate(&modes, lhs, &rhs, &options));
+            }
+        }
+
+        for (
+            name,
+            UserCommand {
+                rhs,
+                lua,
+                nargs,
+                range,
+                count,
+                addr,
+                bang,
+                complete,
+            },
+        ) in &config.commands
+        {
+            let body = match (rhs, lua) {
+                (Some(rhs), None) => Body::Cmd(rhs.clone()),
+                (None, Some(lua)) => Body::Lua(lua.trim_end().to_string()),
+                _ => unreachable!("checked in validate"),
+            };
+            let bang = CommandAttribute::Bool(*bang);
+            let complete = complete.clone().map(CommandAttribute::String);
+            let addr = addr.clone().map(CommandAttribute::String);
+            let attributes: Vec<_> = [
+                ("nargs", nargs.as_ref()),
+                ("range", range.as_ref()),
+                ("count", count.as_ref()),
+                ("addr", addr.as_ref()),
+                ("bang", Some(&bang)),
+                ("complete", complete.as_ref()),
+            ]
+            .iter()
+            .filter_map(|(attribute, value)| value.map(|value| (*attribute, value)))
+            .collect();
+            let target = out.path(backend, &None, "config", output);
+            record(
+                "command",
+                name,
+                None,
+                &target,
+                lines.key(&["commands", name]),
+            );
+            out.lines(backend, &None, "config", output)
+                .push(backend.command(name, body, &attributes));
+        }
+
+        {
+            let target = out.path(backend, &None, "options", output);
+            let global = out.lines(backend, &None, "options", output);
+
+            for (index, option) in config.set_default.iter().enumerate() {
+                let line = lines.item(&["set_default"], index, config.set_default.len());
+                record("option", option, None, &target, line);
+                global.push(backend.set(false, &format!("{}&", option)));
+            }
+
+            for (index, set) in config.set.iter().enumerate() {
+                let line = lines.item(&["set"], index, config.set.len());
+                record("option", set, None, &target, line);
+                global.push(backend.set(false, set));
+            }
+
+            for (name, value) in &config.set_value {
+                let line = lines.key(&["set_value", name]);
+                record("option", name, None, &target, line);
+                let value = expand_value(value.clone())
+                    .with_context(|| format!("In option `{}` in {}", name, filename))?;
+                global.push(backend.set_value(false, name, &value));
+            }
+        }
+
+        {
+            // Variables like `mapleader` have to be set before the mappings, so they are not split
+            // off, as `plugin/config` is loaded before `plugin/keys`
+            let target = out.path(backend, &None, "config", output);
+            let global = out.lines(backend, &None, "config", output);
+
+            for (index, name) in config.unlet.iter().enumerate() {
+                let line = lines.item(&["unlet"], index, config.unlet.len());
+                record("variable", name, None, &target, line);
+                global.push(backend.unlet(name));
+            }
+
+            for (name, value, path) in variables(&config.r#let, None) {
+                let line = lines.key(&[&["let"], path.as_slice()].concat());
+                record("variable", &name, None, &target, line);
+                let value = expand_value(value)
+                    .with_context(|| format!("In variable `{}` in {}", name, filename))?;
+                global.push(backend.r#let(&name, &value));
+            }
+
+            for (name, highlight) in &config.highlights {
+                let line = lines.key(&["highlights", name]);
+                record("highlight", name, None, &target, line);
+                global.push(backend.highlight(name, highlight));
+            }
+
+            for Source { path, content } in &config.source {
+                global.push(backend.comment(&format!("Source: {}", path)));
+                global.push(backend.source(path, content));
+            }
+        }
+
+        if !config.plugins.is_empty() {
+            if backend == Backend::Lua {
+                plugins.push(backend.comment(&format!("File: {}", filename)));
+                plugins.extend(config.plugins.iter().map(backend::lazy_spec));
+            } else {
+                eprintln!(
+                    "Warning: Ignoring plugins in {}, they are only supported by the lua backend",
+                    filename
+                );
+            }
+        }
+
+        for (file_type, options) in &config.file_type {
+            let target = out.path(backend, &Some(file_type.clone()), "config", output);
+            let local = out.lines(backend, &Some(file_type.clone()), "config", output);
+
+            for (index, set) in options.set.iter().enumerate() {
+                let line = lines.item(&["file_type", file_type, "set"], index, options.set.len());
+                record("option", set, None, &target, line);
+                local.push(backend.set(true, set));
+            }
+
+            for (name, value) in &options.set_value {
+                let line = lines.key(&["file_type", file_type, "set_value", name]);
+                record("option", name, None, &target, line);
+                let value = expand_value(value.clone()).with_context(|| {
+                    format!("In option `{}` for {} in {}", name, file_type, filename)
+                })?;
+                local.push(backend.set_value(true, name, &value));
+            }
+
+            for (name, value, path) in variables(&options.r#let, Some("b")) {
+                let line = lines.key(&[&["file_type", file_type, "let"], path.as_slice()].concat());
+                record("variable", &name, None, &target, line);
+                let value = expand_value(value).with_context(|| {
+                    format!("In variable `{}` for {} in {}", name, file_type, filename)
+                })?;
+                local.push(backend.r#let(&name, &value));
+            }
+        }
+
+        if !guards.is_empty() {
+            for (file, lines) in &mut out.files {
+                let start = starts.get(file).copied().unwrap_or_default();
+                if lines.len() > start {
+                    let guarded = lines.split_off(start);
+                    lines.extend(backend.guard(&guards, guarded));
+                }
+            }
+        }
+        for (file, lines) in &mut out.plugin_files {
+            let start = plugin_starts.get(file).copied().unwrap_or_default();
+            if lines.len() > start {
+                if !guards.is_empty() {
+                    let guarded = lines.split_off(start);
+                    lines.extend(backend.guard(&guards, guarded));
+                }
+                // The header of a file with an `output` is already part of it
+                if output.is_none() {
+                    lines.insert(
+                        start,
+                        format!("\n\n{}", backend.comment(&format!("File: {}", filename))),
+                    );
+                }
+            }
+        }
+    }
+
+    if !leaders.is_empty() {
+        let leader_lines = |backend: Backend| {
+            let mut lines = vec![backend.comment("Leader keys, set before any mapping:")];
+            for (variable, value, when) in &leaders {
+                let line = backend.let_literal(variable, value);
+                let guards = runtime_checks(backend, when).unwrap_or_default();
+                if guards.is_empty() {
+                    lines.push(line);
+                } else {
+                    lines.extend(backend.guard(&guards, vec![line]));
+                }
+            }
+            lines
+        };
+        // `<LEADER>` is resolved when a mapping is defined, and files with an `output`, of
+        // another backend or of a split section can be sourced before `plugin/config`
+        let has_leader_mappings = |lines: &[String]| {
+            lines
+                .iter()
+                .any(|line| line.contains("<LEADER>") || line.contains("<LOCALLEADER>"))
+        };
+        out.lines(default_backend, &None, "config", None);
+        for ((backend, file_type), lines) in &mut out.files {
+            if file_type.is_none() && (*backend == default_backend || has_leader_mappings(lines)) {
+                lines.splice(0..0, leader_lines(*backend));
+            }
+        }
+        for ((backend, _), lines) in &mut out.plugin_files {
+            if has_leader_mappings(lines) {
+                lines.splice(0..0, leader_lines(*backend));
+            }
+        }
+    }
+
+    if !shared_autocmds.is_empty() {
+        let vimscript = out.lines(default_backend, &None, "autocmds", None);
+        vimscript.push(format!(
+            "\n\n{}",
+            default_backend.comment("Augroups shared between files:")
+        ));
+        for (group, (_, autocmds)) in shared_autocmds {
+            vimscript.extend(default_backend.augroup(&group, true));
+            vimscript.extend(autocmds);
+            vimscript.extend(default_backend.augroup_end());
+        }
+    }
+
+    warn_prefixes(&mappings);
+
+    let plugins = if plugins.is_empty() {
+        None
+    } else {
+        let plugins = plugins
+            .iter()
+            .map(|plugin| backend::indent(plugin, 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(format!("return {{\n{}\n}}", plugins))
+    };
+    let join = |backend: Backend, lines: Vec<String>| {
+        let text = lines.join("\n");
+        if format {
+            backend.align_mappings(&text)
+        } else {
+            text
+        }
+    };
+    Ok(GeneratedFiles {
+        files: out
+            .files
+            .into_iter()
+            .map(|((backend, file_type), lines)| ((backend, file_type), join(backend, lines)))
+            .collect(),
+        plugin_files: out
+            .plugin_files
+            .into_iter()
+            .map(|((backend, name), lines)| ((backend, name), join(backend, lines)))
+            .collect(),
+        plugins,
+        manifest: manifest.then(|| manifest::to_json(&entries)),
+        counts,
+    })
+}
@@ -0,0 +1,277 @@
+//! The lines the entries of a config file start at, for the [`manifest`](crate::manifest). They
+//! come from the parser of each format, so an option or event used twice gets the line of each
+//! use instead of the first one containing its name.
+
+use std::collections::HashMap;
+
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use toml::Spanned;
+use yaml_rust::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+};
+
+use crate::json;
+
+/// The 1-based lines of the entries of a config file by their path of keys and list indexes, e.g.
+/// `["auto_commands", "1"]` for the second autocommand. Map entries start at their key, list
+/// items at the item.
+#[derive(Default)]
+pub(crate) struct Lines {
+    lines: HashMap<Vec<String>, usize>,
+    /// Where the values of map entries start, if not on the line of their key.
+    values: HashMap<Vec<String>, usize>,
+    /// The number of items of each list.
+    lengths: HashMap<Vec<String>, usize>,
+}
+
+impl Lines {
+    /// The lines of `text` in `format` (see [`format_of`](crate::format_of)), none if it does not
+    /// parse.
+    pub(crate) fn of(format: Option<&str>, text: &str) -> Self {
+        match format {
+            Some("yaml" | "yml") => yaml(text),
+            Some("toml") => toml(text),
+            Some("json") => json::lines(text),
+            _ => Lines::default(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, path: &[String], line: usize) {
+        self.lines.entry(path.to_vec()).or_insert(line);
+    }
+
+    pub(crate) fn insert_value(&mut self, path: &[String], line: usize) {
+        if self.lines.get(path) != Some(&line) {
+            self.values.insert(path.to_vec(), line);
+        }
+    }
+
+    pub(crate) fn set_length(&mut self, path: &[String], length: usize) {
+        self.lengths.insert(path.to_vec(), length);
+    }
+
+    /// The line of the map entry at `path`.
+    pub(crate) fn key(&self, path: &[&str]) -> Option<usize> {
+        let path: Vec<_> = path.iter().map(|key| key.to_string()).collect();
+        self.lines.get(&path).copied()
+    }
+
+    /// The line of the `index`th of the `length` items of the list at `path`. A single item can
+    /// be given without the list, and the items of a config this one `extends` come before its
+    /// own, which are the last ones.
+    pub(crate) fn item(&self, path: &[&str], index: usize, length: usize) -> Option<usize> {
+        let mut path: Vec<_> = path.iter().map(|key| key.to_string()).collect();
+        match self.lengths.get(&path) {
+            Some(own) => {
+                let index = index.checked_sub(length.checked_sub(*own)?)?;
+                path.push(index.to_string());
+                self.lines.get(&path).copied()
+            }
+            None if index + 1 == length => self
+                .values
+                .get(&path)
+                .or_else(|| self.lines.get(&path))
+                .copied(),
+            None => None,
+        }
+    }
+}
+
+/// The 1-based line of the byte `offset`, by the `starts` of the lines.
+pub(crate) fn line_at(starts: &[usize], offset: usize) -> usize {
+    starts.partition_point(|start| *start <= offset)
+}
+
+/// The byte offsets the lines of `text` start at.
+pub(crate) fn line_starts(text: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(text.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
+}
+
+fn yaml(text: &str) -> Lines {
+    struct Events(Vec<(Event, Marker)>);
+    impl MarkedEventReceiver for Events {
+        fn on_event(&mut self, event: Event, mark: Marker) {
+            self.0.push((event, mark));
+        }
+    }
+
+    /// Records the entries of the node starting with `event` below `path`.
+    fn node(
+        event: Event,
+        events: &mut std::vec::IntoIter<(Event, Marker)>,
+        path: &mut Vec<String>,
+        lines: &mut Lines,
+    ) {
+        match event {
+            Event::MappingStart(_) => {
+                while let Some((key, mark)) = events.next() {
+                    if key == Event::MappingEnd {
+                        break;
+                    }
+                    let Some((value, value_mark)) = events.next() else {
+                        break;
+                    };
+                    match key {
+                        Event::Scalar(key, ..) => {
+                            path.push(key);
+                            lines.insert(path, mark.line());
+                            lines.insert_value(path, value_mark.line());
+                            node(value, events, path, lines);
+                            path.pop();
+                        }
+                        // Complex keys are never config entries
+                        key => {
+                            node(key, events, path, &mut Lines::default());
+                            node(value, events, path, &mut Lines::default());
+                        }
+                    }
+                }
+            }
+            Event::SequenceStart(_) => {
+                let mut length = 0;
+                while let Some((event, mark)) = events.next() {
+                    if event == Event::SequenceEnd {
+                        break;
+                    }
+                    path.push(length.to_string());
+                    lines.insert(path, mark.line());
+                    node(event, events, path, lines);
+                    path.pop();
+                    length += 1;
+                }
+                lines.set_length(path, length);
+            }
+            _ => {}
+        }
+    }
+
+    let mut events = Events(Vec::new());
+    let mut lines = Lines::default();
+    if Parser::new(text.chars()).load(&mut events, false).is_err() {
+        return lines;
+    }
+    let mut events = events.0.into_iter();
+    while let Some((event, _)) = events.next() {
+        if event == Event::DocumentStart {
+            if let Some((event, _)) = events.next() {
+                node(event, &mut events, &mut Vec::new(), &mut lines);
+            }
+            break;
+        }
+    }
+    lines
+}
+
+/// A TOML value with the spans toml reports: the ones of keys and of scalars, tables have none.
+enum TomlNode {
+    Table(Vec<(Spanned<String>, Spanned<TomlNode>)>),
+    Array(Vec<Spanned<TomlNode>>),
+    Scalar,
+}
+
+impl<'de> Deserialize<'de> for TomlNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NodeVisitor;
+        impl<'de> Visitor<'de> for NodeVisitor {
+            type Value = TomlNode;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a TOML value")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(TomlNode::Table(entries))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut items = Vec::new();
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(TomlNode::Array(items))
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<Self::Value, E> {
+                Ok(TomlNode::Scalar)
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E> {
+                Ok(TomlNode::Scalar)
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E> {
+                Ok(TomlNode::Scalar)
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E> {
+                Ok(TomlNode::Scalar)
+            }
+
+            fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+                Ok(TomlNode::Scalar)
+            }
+        }
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+fn toml(text: &str) -> Lines {
+    /// Records the entries of `node` below `path`, returning the first line in it.
+    fn visit(
+        node: &TomlNode,
+        starts: &[usize],
+        path: &mut Vec<String>,
+        lines: &mut Lines,
+    ) -> Option<usize> {
+        match node {
+            TomlNode::Table(entries) => {
+                let mut first = None;
+                for (key, value) in entries {
+                    let line = line_at(starts, key.start());
+                    path.push(key.get_ref().clone());
+                    lines.insert(path, line);
+                    visit(value.get_ref(), starts, path, lines);
+                    path.pop();
+                    first = Some(first.map_or(line, |first: usize| first.min(line)));
+                }
+                first
+            }
+            TomlNode::Array(items) => {
+                let mut first = None;
+                for (index, item) in items.iter().enumerate() {
+                    path.push(index.to_string());
+                    let inner = visit(item.get_ref(), starts, path, lines);
+                    // The tables of `[[section]]` have no span, they start at their first key
+                    let line = match item.span() {
+                        (0, 0) => inner,
+                        (start, _) => Some(line_at(starts, start)),
+                    };
+                    if let Some(line) = line {
+                        lines.insert(path, line);
+                        first = Some(first.map_or(line, |first: usize| first.min(line)));
+                    }
+                    path.pop();
+                }
+                lines.set_length(path, items.len());
+                first
+            }
+            TomlNode::Scalar => None,
+        }
+    }
+
+    let mut lines = Lines::default();
+    if let Ok(node) = toml::from_str::<TomlNode>(text) {
+        visit(&node, &line_starts(text), &mut Vec::new(), &mut lines);
+    }
+    lines
+}
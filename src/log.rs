@@ -0,0 +1,45 @@
+//! Leveled messages on stderr for `--quiet` and `--verbose`. Warnings and errors are always
+//! printed, only the informational messages depend on the level.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much is printed besides warnings and errors.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Level {
+    /// Nothing, not even the summary (`--quiet`).
+    Quiet,
+    /// The summary of a run.
+    Info,
+    /// Every file parsed and written (`--verbose`).
+    Debug,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Whether messages of `level` are printed.
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Prints to stderr unless `--quiet`.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Info) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Prints to stderr with `--verbose`.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            eprintln!($($arg)*);
+        }
+    };
+}
@@ -1,348 +1,466 @@
+#[macro_use]
+mod log;
+
+mod cli;
+mod diff;
+mod profile;
+mod snapshot;
+mod watch;
+
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet},
     ffi::OsStr,
-    fmt::Display,
-    fs::{self, read_dir, File},
-    io::Read,
-    str::FromStr,
+    fs::{self, read_dir, OpenOptions},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
 };
 
 use anyhow::{bail, Context, Result};
-use enumflags2::{bitflags, BitFlags};
-use serde::Deserialize;
-use serde_with::{serde_as, DeserializeFromStr, OneOrMany};
+use nvim_config::{Backend, Options};
+use profile::Profile;
 
-#[serde_as]
-#[derive(Deserialize)]
-struct AutoCommand {
-    #[serde_as(deserialize_as = "OneOrMany<_>")]
-    triggers: Vec<String>,
-    #[serde(default)]
-    #[serde_as(deserialize_as = "OneOrMany<_>")]
-    cmd: Vec<String>,
-    #[serde(default)]
-    #[serde_as(deserialize_as = "OneOrMany<_>")]
-    lua: Vec<String>,
-    matching: Option<String>,
-    #[serde(default)]
-    event: HashMap<String, String>,
-    #[serde(default)]
-    silent: bool,
-    #[serde(default)]
-    file_type: Option<String>,
+fn main() -> Result<()> {
+    let args = cli::Args::parse()?;
+    log::set_level(if args.quiet {
+        log::Level::Quiet
+    } else if args.verbose {
+        log::Level::Debug
+    } else {
+        log::Level::Info
+    });
+    let result = if let Some(path) = &args.import {
+        import(path)
+    } else if args.init {
+        init(&args)
+    } else if args.validate {
+        validate_all(&args)
+    } else if args.list_file_types {
+        list_file_types(&args)
+    } else if args.watch {
+        watch::watch(&args)
+    } else {
+        generate(&args).map(drop)
+    };
+    if let Err(error) = result {
+        print_errors(&args, vec![error]);
+        std::process::exit(1);
+    }
+    Ok(())
 }
 
-#[serde_as]
-#[derive(Deserialize)]
-struct Config {
-    #[serde(default)]
-    #[serde_as(deserialize_as = "OneOrMany<_>")]
-    auto_commands: Vec<AutoCommand>,
-    #[serde(default)]
-    keys: HashMap<MapFlags, HashMap<String, MaybePrefixedMapping>>,
-    #[serde(default)]
-    #[serde_as(deserialize_as = "OneOrMany<_>")]
-    set: Vec<String>,
-    #[serde(default)]
-    set_value: HashMap<String, Value>,
-    #[serde(default)]
-    r#let: HashMap<String, Value>,
+/// Prints `errors` on stderr in the format of `--error-format`.
+fn print_errors(args: &cli::Args, errors: Vec<anyhow::Error>) {
+    match args.error_format {
+        cli::ErrorFormat::Human => {
+            for error in errors {
+                eprintln!("Error: {:?}", error);
+            }
+        }
+        cli::ErrorFormat::Json => {
+            // The config read from stdin has no dir
+            let config_dir = match args.stdin {
+                Some(_) => Path::new(""),
+                None => &args.config_dir,
+            };
+            eprint!("{}", nvim_config::errors_to_json(&errors, config_dir));
+        }
+    }
 }
 
-#[derive(Deserialize)]
-#[serde(untagged)]
-enum Value {
-    Int(i64),
-    String(String),
-    Bool(bool),
-}
-impl Display for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Value::Int(value) => write!(f, "{value}"),
-            Value::String(value) => write!(f, r#""{value}""#),
-            Value::Bool(true) => write!(f, "yes"),
-            Value::Bool(false) => write!(f, "no"),
+/// Previously generated `<ft>_config.<ext>` files in `ft_plugin_dir` that are not part of
+/// `files` anymore. Anything not following that naming scheme is left alone.
+fn stale_files(
+    ft_plugin_dir: &Path,
+    backend: Backend,
+    files: &[(PathBuf, String)],
+) -> Result<Vec<PathBuf>> {
+    if !ft_plugin_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let suffix = format!("_config.{}", backend.extension());
+    let mut stale = Vec::new();
+    for entry in read_dir(ft_plugin_dir)? {
+        let path = entry?.path();
+        let generated = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .and_then(|name| name.strip_suffix(&suffix))
+            .is_some_and(|file_type| !file_type.is_empty());
+        if generated && path.is_file() && !files.iter().any(|(file, _)| file == &path) {
+            stale.push(path);
         }
     }
+    stale.sort();
+    Ok(stale)
 }
 
-#[bitflags]
-#[repr(u8)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum MapFlag {
-    Insert,
-    Normal,
-    Visual,
-    Leader,
-    Command,
-    Recursive,
+/// Previously generated files in `plugin_dir` that are not part of `files` anymore, like the
+/// sections of `--split` or the `output` of a config file. Only files starting with the banner
+/// are considered, so hand-written plugins are left alone.
+fn stale_plugin_files(
+    plugin_dir: &Path,
+    backend: Backend,
+    files: &[(PathBuf, String)],
+) -> Result<Vec<PathBuf>> {
+    if !plugin_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let marker = backend.comment("AUTO-GENERATED by nvim-config");
+    let mut stale = Vec::new();
+    for entry in read_dir(plugin_dir)? {
+        let path = entry?.path();
+        let generated = path.extension() == Some(OsStr::new(backend.extension()))
+            && fs::read_to_string(&path).is_ok_and(|content| content.starts_with(&marker));
+        if generated && !files.iter().any(|(file, _)| file == &path) {
+            stale.push(path);
+        }
+    }
+    stale.sort();
+    Ok(stale)
 }
 
-#[derive(DeserializeFromStr, Hash, PartialEq, Eq)]
-struct MapFlags {
-    flags: BitFlags<MapFlag>,
-    file_type: Option<String>,
-    label: Option<String>,
+/// A short report of what a run generated: the counts per kind, the number of files and the
+/// file type specific ones among them.
+fn summary(
+    counts: &BTreeMap<&str, usize>,
+    files: &[(PathBuf, String)],
+    stale: &[PathBuf],
+    ft_plugin_dir: &Path,
+) -> String {
+    let counts: Vec<_> = counts
+        .iter()
+        .map(|(kind, count)| format!("{} {}{}", count, kind, if *count == 1 { "" } else { "s" }))
+        .collect();
+    let mut summary = format!(
+        "Generated {} in {} file(s)",
+        if counts.is_empty() {
+            "nothing".to_string()
+        } else {
+            counts.join(", ")
+        },
+        files.len()
+    );
+    if !stale.is_empty() {
+        summary += &format!(", removed {} stale file(s)", stale.len());
+    }
+    let file_types: Vec<_> = files
+        .iter()
+        .filter(|(path, _)| path.starts_with(ft_plugin_dir))
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+    if !file_types.is_empty() {
+        summary += &format!("\nFile type files: {}", file_types.join(", "));
+    }
+    summary
 }
 
-impl FromStr for MapFlags {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use MapFlag::*;
-        let mut flags = HashSet::new();
-        let (s, mut label) = match s.split_once("_") {
-            Some((s, label)) => (s, Some(label.to_string())),
-            None => (s, None),
-        };
-        let mut file_type = None;
+/// The config written by `--init`.
+const EXAMPLE: &str = include_str!("example.yaml");
 
-        for c in s.to_ascii_lowercase().chars() {
-            flags.insert(match c {
-                'i' => Insert,
-                'n' => Normal,
-                'v' => Visual,
-                'l' => Leader,
-                'c' => Command,
-                'r' => Recursive,
-                'f' => match (label, file_type) {
-                    (Some(l), None) => {
-                        match l.split_once("_") {
-                            Some((ft, l)) => {
-                                file_type = Some(ft.to_string());
-                                label = Some(l.to_string());
-                            }
-                            None => {
-                                file_type = Some(l.to_string());
-                                label = None;
-                            }
-                        };
-                        continue;
-                    }
-                    (_, Some(_)) => bail!("Duplicate filetype flag not supported: `{}`", s),
-                    (None, _) => bail!("Filetype flag only supported when filetype is given"),
-                },
-                _ => bail!("Unsuported flag for Mapping: `{}`", c),
-            });
+/// Creates the config dir with an example config, never overwriting an existing file.
+fn init(args: &cli::Args) -> Result<()> {
+    fs::create_dir_all(&args.config_dir)
+        .with_context(|| format!("Failed to create config dir: {}", args.config_dir.display()))?;
+    let path = args.config_dir.join("example.yaml");
+    let mut file = match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+            bail!("Refusing to overwrite existing file: {}", path.display())
         }
-        let flags = flags.into_iter().collect();
-        Ok(MapFlags {
-            flags,
-            label,
-            file_type,
-        })
+        Err(error) => {
+            return Err(error).with_context(|| format!("Failed to create file: {}", path.display()))
+        }
+    };
+    file.write_all(EXAMPLE.as_bytes())
+        .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    info!("Created {}", path.display());
+    Ok(())
+}
+
+/// Prints the config `--import` makes of the vimscript file at `path`, with a warning for every
+/// line it does not understand.
+fn import(path: &Path) -> Result<()> {
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let import = nvim_config::import_vim(&source);
+    print!("{}", import.config);
+    for (number, line) in &import.skipped {
+        eprintln!(
+            "Warning: Skipped line {} of {}: {}",
+            number,
+            path.display(),
+            line
+        );
     }
+    info!(
+        "Imported {} with {} skipped line(s)",
+        path.display(),
+        import.skipped.len()
+    );
+    Ok(())
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-enum MaybePrefixedMapping {
-    Mapping(String),
-    PrefixedMappings(HashMap<String, String>),
+/// Checks every config file, reporting all errors grouped by file instead of stopping at the
+/// first one.
+fn validate_all(args: &cli::Args) -> Result<()> {
+    if !args.config_dir.exists() {
+        info!(
+            "Config dir {} does not exist, nothing to validate",
+            args.config_dir.display()
+        );
+        return Ok(());
+    }
+    let results = nvim_config::validate_all(&args.config_dir, args.allow_unknown_events)?;
+    if args.error_format == cli::ErrorFormat::Json {
+        let errors: Vec<_> = results.into_iter().flat_map(|(_, errors)| errors).collect();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        print_errors(args, errors);
+        std::process::exit(1);
+    }
+    let mut failed = 0;
+    let mut count = 0;
+    for (filename, errors) in results {
+        failed += 1;
+        count += errors.len();
+        eprintln!("{}:", filename);
+        for error in errors {
+            let error = format!("{:?}", error).replace('\n', "\n    ");
+            eprintln!("  - {}", error);
+        }
+    }
+    if count > 0 {
+        bail!("Found {} error(s) in {} file(s)", count, failed);
+    }
+    Ok(())
 }
 
-fn main() -> Result<()> {
-    let nvim_dir = dirs::config_dir()
-        .expect("There should be a config_dir")
-        .join("nvim");
-    let config_folder = nvim_dir.join("config");
+/// Prints the file types the config files generate `ftplugin` files for, one per line.
+fn list_file_types(args: &cli::Args) -> Result<()> {
+    if !args.config_dir.exists() {
+        info!(
+            "Config dir {} does not exist, no file types",
+            args.config_dir.display()
+        );
+        return Ok(());
+    }
+    let configs = nvim_config::parse_dir(&args.config_dir)?;
+    let file_types: BTreeSet<_> = configs
+        .iter()
+        .flat_map(|config| config.file_types())
+        .collect();
+    for file_type in file_types {
+        println!("{}", file_type);
+    }
+    Ok(())
+}
 
-    let config_files = read_dir(config_folder)?;
+/// Runs the whole parse-and-generate pipeline, returning the files written.
+fn generate(args: &cli::Args) -> Result<Vec<PathBuf>> {
+    let mut profile = Profile::new();
+    let written = run(args, &mut profile)?;
+    if args.profile {
+        eprint!("{}", profile.report());
+    }
+    Ok(written)
+}
 
-    let mut configs: Vec<(Config, String)> = vec![];
+/// [`generate`], starting a phase of `profile` for each step.
+fn run(args: &cli::Args, profile: &mut Profile) -> Result<Vec<PathBuf>> {
+    let &cli::Args {
+        ref config_dir,
+        ref output_dir,
+        ref stdin,
+        dry_run,
+        diff,
+        check,
+        backend,
+        strict,
+        allow_unknown_events,
+        expand_env,
+        split,
+        manifest,
+        format,
+        line_ending,
+        group_leader,
+        annotate,
+        ..
+    } = args;
 
-    for config_file in config_files {
-        let config_file = config_file?.path();
-        if let (Some(filename), Some(extension)) = (
-            config_file
-                .clone()
-                .file_name()
-                .map(OsStr::to_string_lossy)
-                .map(|s| s.to_string()),
-            config_file.extension(),
-        ) {
-            match extension.to_string_lossy().to_lowercase().as_str() {
-                "yaml" | "yml" => {
-                    configs.push((
-                        serde_yaml::from_reader(File::open(config_file)?)
-                            .with_context(|| format!("Failed to parse file: {}", filename))?,
-                        filename,
-                    ));
-                }
-                "toml" => {
-                    configs.push((
-                        toml::from_str(&{
-                            let mut string = String::new();
-                            File::open(config_file)?.read_to_string(&mut string)?;
-                            string
-                        })
-                        .with_context(|| format!("Failed to parse file: {}", filename))?,
-                        filename,
-                    ));
-                }
-                _ => (),
+    let configs = match stdin {
+        Some(format) => {
+            let mut source = String::new();
+            io::stdin()
+                .read_to_string(&mut source)
+                .context("Failed to read config from stdin")?;
+            profile.phase("parsing");
+            vec![nvim_config::parse_str(&source, format, "stdin")?]
+        }
+        None if !config_dir.exists() => {
+            info!(
+                "Config dir {} does not exist, nothing to generate",
+                config_dir.display()
+            );
+            return Ok(Vec::new());
+        }
+        None => {
+            profile.phase("scanning");
+            let files = nvim_config::config_files(config_dir)?;
+            profile.phase("parsing");
+            let mut configs = Vec::new();
+            for (filename, parsed, duration) in nvim_config::parse_files(files)? {
+                profile.file(&filename, duration);
+                configs.extend(parsed);
             }
+            configs
         }
+    };
+    for config in &configs {
+        debug!("Parsed {}", config.name());
+    }
+    profile.phase("emission");
+    let generated = nvim_config::generate(
+        &configs,
+        &Options {
+            backend,
+            strict,
+            allow_unknown_events,
+            expand_env,
+            split,
+            manifest,
+            format,
+            group_leader,
+            annotate,
+        },
+    )?;
+
+    profile.phase("writing");
+    let plugin_dir = output_dir.join("plugin");
+    let ft_plugin_dir = output_dir.join("ftplugin");
+    let plugins_file = output_dir.join("lua").join("plugins").join("generated.lua");
+    let origin = match stdin {
+        Some(_) => "the config read from stdin".to_string(),
+        None => format!("the files in {}", config_dir.display()),
+    };
+    let banner = |backend: Backend| {
+        backend.comment(&format!(
+            "AUTO-GENERATED by nvim-config - do not edit, edit {} instead",
+            origin
+        ))
+    };
+    let mut files: Vec<_> = generated
+        .files
+        .into_iter()
+        // Sorted for a stable order of the output
+        .collect::<BTreeMap<_, _>>()
+        .into_iter()
+        .map(|((backend, file_type), content)| {
+            let path = match file_type {
+                None => plugin_dir.join(format!("config.{}", backend.extension())),
+                Some(file_type) => {
+                    ft_plugin_dir.join(format!("{}_config.{}", file_type, backend.extension()))
+                }
+            };
+            (path, format!("{}\n{}", banner(backend), content))
+        })
+        .collect();
+    // Config files can choose their own backend, so files of either one can be stale
+    let backends = [Backend::Vim, Backend::Lua];
+    let mut stale = Vec::new();
+    for backend in backends {
+        stale.extend(stale_files(&ft_plugin_dir, backend, &files)?);
+    }
+    files.extend(
+        generated
+            .plugin_files
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|((backend, name), content)| {
+                let path = plugin_dir.join(format!("{}.{}", name, backend.extension()));
+                (path, format!("{}\n{}", banner(backend), content))
+            }),
+    );
+    for backend in backends {
+        stale.extend(stale_plugin_files(&plugin_dir, backend, &files)?);
+    }
+    let manifest_file = plugin_dir.join(".nvim-config-manifest.json");
+    if let Some(manifest) = generated.manifest {
+        // JSON has no comments, so there is no banner
+        files.push((manifest_file, manifest));
+    } else if manifest_file.is_file() {
+        stale.push(manifest_file);
+    }
+    if let Some(plugins) = generated.plugins {
+        files.push((
+            plugins_file,
+            format!("{}\n{}", banner(Backend::Lua), plugins),
+        ));
+    } else if backend == Backend::Lua && plugins_file.is_file() {
+        stale.push(plugins_file);
     }
-    let mut vimscript: HashMap<Option<String>, Vec<String>> = HashMap::new();
-    fn mut_or_default<'map>(
-        map: &'map mut HashMap<Option<String>, Vec<String>>,
-        key: &Option<String>,
-    ) -> &'map mut Vec<String> {
-        if !map.contains_key(key) {
-            map.insert(key.clone(), Vec::new());
+
+    if line_ending == cli::LineEnding::Crlf {
+        for (_, content) in &mut files {
+            *content = content.replace('\n', "\r\n");
         }
-        map.get_mut(key).expect("Inserted missing key")
     }
 
-    for (config, filename) in configs {
-        {
-            let vimscript = mut_or_default(&mut vimscript, &None);
-            vimscript.push(format!("\n\n\" File: {}", filename));
-            vimscript.push("\n\" Keybindings:".to_string());
+    if check {
+        let outdated: Vec<_> = files
+            .into_iter()
+            .filter(|(path, content)| fs::read_to_string(path).ok().as_ref() != Some(content))
+            .map(|(path, _)| path)
+            .chain(stale)
+            .map(|path| path.display().to_string())
+            .collect();
+        if !outdated.is_empty() {
+            bail!("Generated files are out of date:\n{}", outdated.join("\n"));
         }
-        for (
-            MapFlags {
-                flags,
-                label,
-                file_type,
-            },
-            k,
-        ) in config.keys
-        {
-            let vimscript = mut_or_default(&mut vimscript, &file_type);
-            if let Some(label) = label {
-                vimscript.push(format!("\" {}", label));
-            }
-            let mut kbs: Vec<(String, String)> = Vec::new();
-            for (key, binding) in k {
-                match binding {
-                    MaybePrefixedMapping::Mapping(binding) => {
-                        kbs.push((key, binding));
-                    }
-                    MaybePrefixedMapping::PrefixedMappings(binding) => {
-                        for (suffix, binding) in binding {
-                            kbs.push((format!("{}{}", key, suffix), binding));
-                        }
-                    }
-                }
-            }
-            let cmd = if flags.contains(MapFlag::Recursive) {
-                "map"
-            } else {
-                "noremap"
+    } else if diff {
+        let summary = summary(&generated.counts, &files, &stale, &ft_plugin_dir);
+        for (path, content) in &files {
+            let name = path.display().to_string();
+            let diff = match fs::read_to_string(path) {
+                Ok(old) => diff::unified(&name, &name, &old, content),
+                Err(_) => diff::unified("/dev/null", &name, "", content),
             };
-            for (mut key, mut binding) in kbs {
-                if flags.contains(MapFlag::Leader) {
-                    key = format!("<LEADER>{}", key);
-                }
-                binding = binding.replace('|', r"\|");
-                if flags.contains(MapFlag::Command) {
-                    binding = format!("<CMD>{}<CR>", binding);
-                }
-                let cmd = format!(
-                    "{} <silent> {} {}",
-                    cmd,
-                    key.split_ascii_whitespace().collect::<String>(),
-                    binding
-                );
-                if flags.contains(MapFlag::Insert) {
-                    vimscript.push(format!("i{}", cmd));
-                }
-                if flags.contains(MapFlag::Normal) {
-                    vimscript.push(format!("n{}", cmd));
-                }
-                if flags.contains(MapFlag::Visual) {
-                    vimscript.push(format!("v{}", cmd));
-                }
-            }
+            print!("{}", diff.unwrap_or_default());
         }
-
-        for AutoCommand {
-            triggers,
-            cmd,
-            lua,
-            matching,
-            event,
-            silent,
-            file_type,
-        } in config.auto_commands
-        {
-            let vimscript = mut_or_default(&mut vimscript, &None);
-            let triggers = triggers.join(",");
-            let matching = matching.unwrap_or_else(|| {
-                if file_type.is_some() {
-                    "<buffer>".to_string()
-                } else {
-                    "*".to_string()
-                }
-            });
-            let silent = if silent { "silent!" } else { "" };
-            let condition = event
-                .iter()
-                .map(|(key, value)| format!("v:event.{} is '{}'", key, value))
-                .collect::<Vec<_>>()
-                .join(" && ");
-
-            for cmd in cmd
-                .into_iter()
-                .chain(lua.iter().map(|value| format!("lua {}", value)))
-            {
-                if condition.is_empty() {
-                    vimscript.push(format!(
-                        "autocmd {} {} {} {}",
-                        triggers, matching, silent, cmd
-                    ))
-                } else {
-                    vimscript.push(format!(
-                        "autocmd {} {} {} if {} | execute '{}' | endif",
-                        triggers,
-                        matching,
-                        silent,
-                        condition,
-                        cmd.replace('\'', r"\'")
-                    ))
-                }
-            }
+        for path in &stale {
+            let old = fs::read_to_string(path).unwrap_or_default();
+            let diff = diff::unified(&path.display().to_string(), "/dev/null", &old, "");
+            print!("{}", diff.unwrap_or_default());
         }
-
-        {
-            // TODO implemnt file_type for set
-            let global = mut_or_default(&mut vimscript, &None);
-
-            for set in config.set {
-                global.push(format!("set {}", set));
-            }
-
-            for (name, value) in config.set_value {
-                global.push(format!(r#"set {}={}"#, name, value));
-            }
-
-            for (name, value) in config.r#let {
-                global.push(format!("let {}={}", name, value));
-            }
+        info!("{}", summary);
+    } else if dry_run {
+        let summary = summary(&generated.counts, &files, &stale, &ft_plugin_dir);
+        for (path, content) in files {
+            println!("==> {} <==\n{}\n", path.display(), content);
         }
-    }
-
-    for vimscript in vimscript {
-        let ft_plugin_dir = nvim_dir.join("ftplugin");
-        match vimscript {
-            (None, vimscript) => {
-                let plugin_dir = nvim_dir.join("plugin");
-                fs::create_dir_all(&plugin_dir)?;
-
-                fs::write(plugin_dir.join("config.vim"), vimscript.join("\n"))?;
-            }
-            (Some(file_type), vimscript) => {
-                fs::create_dir_all(&ft_plugin_dir)?;
-
-                fs::write(
-                    ft_plugin_dir.join(file_type + "_config.vim"),
-                    vimscript.join("\n"),
-                )?;
+        for path in stale {
+            println!("==> {} <== (removed)\n", path.display());
+        }
+        info!("{}", summary);
+    } else {
+        let summary = summary(&generated.counts, &files, &stale, &ft_plugin_dir);
+        let mut written = Vec::new();
+        for (path, content) in files {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
             }
+            fs::write(&path, content)?;
+            debug!("Wrote {}", path.display());
+            written.push(path);
         }
+        for path in stale {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove stale file: {}", path.display()))?;
+            debug!("Removed stale file {}", path.display());
+        }
+        info!("{}", summary);
+        return Ok(written);
     }
-    Ok(())
+    Ok(Vec::new())
 }
@@ -0,0 +1,56 @@
+/// Where a generated mapping, autocommand, option etc. came from and where it ended up.
+pub struct Entry {
+    /// E.g. `mapping`, `autocommand` or `option`.
+    pub kind: &'static str,
+    /// The lhs, event, option or variable name.
+    pub name: String,
+    /// The mode letter of mappings and abbreviations.
+    pub mode: Option<char>,
+    /// The config file.
+    pub source: String,
+    /// The 1-based line the entry starts at in the config file, if the parser reports it.
+    pub line: Option<usize>,
+    /// The generated file, relative to the output dir.
+    pub output: String,
+}
+
+/// Serializes `entries` as a JSON array of objects.
+pub fn to_json(entries: &[Entry]) -> String {
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            let optional = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+            format!(
+                "  {{\"kind\": {}, \"name\": {}, \"mode\": {}, \"source\": {}, \"line\": {}, \"output\": {}}}",
+                json_string(entry.kind),
+                json_string(&entry.name),
+                optional(entry.mode.map(|mode| json_string(&mode.to_string()))),
+                json_string(&entry.source),
+                optional(entry.line.map(|line| line.to_string())),
+                json_string(&entry.output)
+            )
+        })
+        .collect();
+    if entries.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", entries.join(",\n"))
+    }
+}
+
+pub(crate) fn json_string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    string.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str(r#"\""#),
+            '\\' => string.push_str(r"\\"),
+            '\n' => string.push_str(r"\n"),
+            '\t' => string.push_str(r"\t"),
+            c if c.is_control() => string.push_str(&format!("\\u{:04x}", c as u32)),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
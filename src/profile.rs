@@ -0,0 +1,60 @@
+//! `--profile`: wall-clock time of each phase of a run, reported as a table on stderr.
+
+use std::time::{Duration, Instant};
+
+/// The phases of a run, each lasting until the next one starts.
+pub struct Profile {
+    phases: Vec<(&'static str, Instant)>,
+    /// The parse time of each config file, they are parsed in parallel.
+    files: Vec<(String, Duration)>,
+}
+
+impl Profile {
+    pub fn new() -> Self {
+        Self {
+            phases: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Ends the current phase and starts `name`.
+    pub fn phase(&mut self, name: &'static str) {
+        self.phases.push((name, Instant::now()));
+    }
+
+    pub fn file(&mut self, name: &str, duration: Duration) {
+        self.files.push((name.to_string(), duration));
+    }
+
+    /// The table of the phases, ending the current one, with the files below parsing.
+    pub fn report(&self) -> String {
+        let end = Instant::now();
+        let Some((_, start)) = self.phases.first() else {
+            return String::new();
+        };
+        let mut rows = Vec::new();
+        for (index, (name, phase_start)) in self.phases.iter().enumerate() {
+            let phase_end = self.phases.get(index + 1).map_or(end, |(_, next)| *next);
+            rows.push((name.to_string(), phase_end - *phase_start));
+            if *name == "parsing" {
+                rows.extend(
+                    self.files
+                        .iter()
+                        .map(|(file, duration)| (format!("  {}", file), *duration)),
+                );
+            }
+        }
+        rows.push(("total".to_string(), end - *start));
+        let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        let mut table = format!("{:width$}  {:>10}\n", "phase", "time", width = width);
+        for (name, duration) in rows {
+            table += &format!(
+                "{:width$}  {:>8.2}ms\n",
+                name,
+                duration.as_secs_f64() * 1000.0,
+                width = width
+            );
+        }
+        table
+    }
+}
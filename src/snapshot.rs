@@ -0,0 +1,135 @@
+//! The change detection of `--watch`, see [`Snapshot`].
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// The modification time and size of every file in a config dir and its subdirectories.
+///
+/// `--watch` compares these every few hundred milliseconds instead of subscribing to file system
+/// events: a config dir only has a handful of files, polling needs no platform specific
+/// dependency and also sees changes on network and container mounts, where events often do not
+/// arrive.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    files: BTreeMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl Snapshot {
+    /// The files in `dir`, a missing dir counts as empty.
+    pub fn of(dir: &Path) -> Self {
+        fn visit(
+            dir: &Path,
+            visited: &mut HashSet<PathBuf>,
+            files: &mut BTreeMap<PathBuf, (Option<SystemTime>, u64)>,
+        ) {
+            // Symlink loops are only followed once
+            if !dir.canonicalize().is_ok_and(|dir| visited.insert(dir)) {
+                return;
+            }
+            for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    visit(&path, visited, files);
+                } else {
+                    // The size catches rewrites within the resolution of the modification time
+                    let metadata = entry.metadata().ok();
+                    let modified = metadata.as_ref().and_then(|meta| meta.modified().ok());
+                    let size = metadata.map_or(0, |meta| meta.len());
+                    files.insert(path, (modified, size));
+                }
+            }
+        }
+        let mut files = BTreeMap::new();
+        visit(dir, &mut HashSet::new(), &mut files);
+        Snapshot { files }
+    }
+
+    /// The files added, removed or modified since `earlier`, in path order.
+    pub fn changes<'a>(&'a self, earlier: &'a Snapshot) -> Vec<&'a Path> {
+        let mut changes: Vec<_> = self
+            .files
+            .iter()
+            .filter(|(path, file)| earlier.files.get(*path) != Some(file))
+            .map(|(path, _)| path.as_path())
+            .chain(
+                earlier
+                    .files
+                    .keys()
+                    .filter(|path| !self.files.contains_key(*path))
+                    .map(PathBuf::as_path),
+            )
+            .collect();
+        changes.sort_unstable();
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes<'a>(snapshot: &'a Snapshot, earlier: &'a Snapshot, dir: &Path) -> Vec<&'a Path> {
+        snapshot
+            .changes(earlier)
+            .into_iter()
+            .map(|path| path.strip_prefix(dir).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn changes_in_config_dir() {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("nvim-config-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(Snapshot::of(&dir), Snapshot::default());
+
+        fs::create_dir_all(dir.join("lua")).unwrap();
+        fs::write(dir.join("keys.yaml"), "keys: {}\n").unwrap();
+        fs::write(dir.join("lua/telescope.lua"), "return {}\n").unwrap();
+        let first = Snapshot::of(&dir);
+        assert_eq!(
+            changes(&first, &Snapshot::default(), &dir),
+            [Path::new("keys.yaml"), Path::new("lua/telescope.lua")]
+        );
+        assert_eq!(Snapshot::of(&dir), first);
+
+        // A rewrite is seen even within the resolution of the modification time
+        fs::write(dir.join("keys.yaml"), "keys:\n  n:\n    x: y\n").unwrap();
+        fs::write(dir.join("set.toml"), "set = []\n").unwrap();
+        let second = Snapshot::of(&dir);
+        assert_eq!(
+            changes(&second, &first, &dir),
+            [Path::new("keys.yaml"), Path::new("set.toml")]
+        );
+
+        fs::remove_file(dir.join("lua/telescope.lua")).unwrap();
+        let third = Snapshot::of(&dir);
+        assert_eq!(
+            changes(&third, &second, &dir),
+            [Path::new("lua/telescope.lua")]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_loop() {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("nvim-config-watch-loop-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("keys.yaml"), "keys: {}\n").unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("loop")).unwrap();
+        let snapshot = Snapshot::of(&dir);
+        assert_eq!(
+            changes(&snapshot, &Snapshot::default(), &dir),
+            [Path::new("keys.yaml")]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,42 @@
+use std::{thread, time::Duration};
+
+use crate::{cli::Args, generate, print_errors, snapshot::Snapshot};
+use anyhow::Result;
+
+/// How often the config dir is checked for changes, see [`Snapshot`] for why it is polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How long the config dir needs to stay unchanged before regenerating, so a burst of saves
+/// only triggers one regeneration.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Regenerates on every change to the config dir, until killed.
+pub fn watch(args: &Args) -> Result<()> {
+    let mut state = None;
+    loop {
+        let mut current = Snapshot::of(&args.config_dir);
+        if state.as_ref() == Some(&current) {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        // Wait for the changes to settle
+        loop {
+            thread::sleep(DEBOUNCE);
+            let next = Snapshot::of(&args.config_dir);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        if let Some(state) = &state {
+            for path in current.changes(state) {
+                debug!("Changed: {}", path.display());
+            }
+        }
+        state = Some(current);
+
+        match generate(args) {
+            Ok(_) => info!("Regenerated from {}", args.config_dir.display()),
+            Err(error) => print_errors(args, vec![error]),
+        }
+    }
+}
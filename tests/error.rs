@@ -0,0 +1,113 @@
+//! Library users can tell the kinds of failures apart, see the `error.txt` of the fixtures for
+//! the messages.
+
+use std::path::Path;
+
+use nvim_config::{
+    errors_to_json, generate, parse_config, parse_dir, parse_str, validate_all, Error, Options,
+};
+
+fn fixture(name: &str) -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+        .join("config")
+}
+
+#[test]
+fn error_kinds() {
+    let error = parse_dir(&fixture("invalid_flag")).err().unwrap();
+    match error.downcast_ref::<Error>() {
+        Some(Error::Flag { file, flag, .. }) => {
+            assert_eq!(file, "flags.yaml");
+            assert_eq!(flag, "nz");
+        }
+        other => panic!("Expected a flag error, got {:?}", other),
+    }
+
+    let error = parse_dir(&fixture("invalid_toml")).err().unwrap();
+    assert!(matches!(
+        error.downcast_ref::<Error>(),
+        Some(Error::Parse { file, .. }) if file == "broken.toml"
+    ));
+
+    let error = parse_config(&fixture("missing.yaml")).err().unwrap();
+    assert!(matches!(
+        error.downcast_ref::<Error>(),
+        Some(Error::Io { .. })
+    ));
+
+    // Only `parse_dir` splits YAML files into their documents
+    let error = parse_config(&fixture("multi_document").join("init.yaml"))
+        .err()
+        .unwrap();
+    assert!(matches!(
+        error.downcast_ref::<Error>(),
+        Some(Error::Parse { file, .. }) if file == "init.yaml"
+    ));
+    let error = parse_dir(&fixture("multi_document_error")).err().unwrap();
+    assert!(matches!(
+        error.downcast_ref::<Error>(),
+        Some(Error::Flag { file, .. }) if file == "init.yaml#2"
+    ));
+
+    let configs = parse_dir(&fixture("multiline_option")).unwrap();
+    let options = Options::default();
+    let error = generate(&configs, &options).err().unwrap();
+    assert!(matches!(
+        error.downcast_ref::<Error>(),
+        Some(Error::Validation { file, .. }) if file == "statusline.yaml"
+    ));
+}
+
+#[test]
+fn abbreviation_flags() {
+    let error = parse_str(
+        "abbreviations:\n  ib:\n    teh: the\n  iz:\n    adn: and\n",
+        "yaml",
+        "abbreviations.yaml",
+    )
+    .err()
+    .unwrap();
+    match error.downcast_ref::<Error>() {
+        Some(Error::Flag { flag, location, .. }) => {
+            assert_eq!(flag, "iz");
+            assert_eq!(*location, Some((4, 3)));
+        }
+        other => panic!("Expected a flag error, got {:?}", other),
+    }
+
+    // The invalid flags do not carry over to the errors of other files
+    let error = parse_str("keys: [", "yaml", "broken.yaml").err().unwrap();
+    assert!(matches!(
+        error.downcast_ref::<Error>(),
+        Some(Error::Parse { file, .. }) if file == "broken.yaml"
+    ));
+}
+
+#[test]
+fn json() {
+    let error = parse_dir(&fixture("multi_document_error")).err().unwrap();
+    let json = errors_to_json(&[error], Path::new("config"));
+    assert!(
+        json.starts_with(
+            "[\n  {\"file\": \"config/init.yaml\", \"line\": 4, \"column\": 3, \"message\": \"keys: \
+             Unsuported flag for Mapping: `z`"
+        ),
+        "{}",
+        json
+    );
+
+    let errors = validate_all(&fixture("set_conflict"), false)
+        .unwrap()
+        .into_iter()
+        .flat_map(|(_, errors)| errors)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors_to_json(&errors, Path::new("config")),
+        "[\n  {\"file\": \"config/options.yaml\", \"line\": null, \"column\": null, \"message\": \
+         \"Option `wrap` in options.yaml is given in both `set` (as `nowrap`) and `set_value`\"}\n]\n"
+    );
+
+    assert_eq!(errors_to_json(&[], Path::new("config")), "[]\n");
+}
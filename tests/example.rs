@@ -0,0 +1,19 @@
+//! The example config written by `--init` has to stay valid.
+
+use std::path::Path;
+
+use nvim_config::{generate, parse_config, Backend, Options};
+
+#[test]
+fn example_config_generates() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/example.yaml");
+    let config = parse_config(&path).unwrap();
+    for backend in [Backend::Vim, Backend::Lua] {
+        let options = Options {
+            backend,
+            strict: true,
+            ..Options::default()
+        };
+        generate(std::slice::from_ref(&config), &options).unwrap();
+    }
+}
@@ -0,0 +1,27 @@
+//! The file types listed by `--list-filetypes`.
+
+use std::{collections::BTreeSet, path::Path};
+
+use nvim_config::parse_dir;
+
+#[test]
+fn file_types_of_fixtures() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    for (fixture, expected) in [
+        ("nested", &["html", "rust"][..]),
+        ("commands", &["markdown"][..]),
+        ("auto_commands", &[][..]),
+    ] {
+        let configs = parse_dir(&fixtures.join(fixture).join("config")).unwrap();
+        let file_types: BTreeSet<_> = configs
+            .iter()
+            .flat_map(|config| config.file_types())
+            .collect();
+        assert_eq!(
+            file_types,
+            expected.iter().copied().collect(),
+            "{}",
+            fixture
+        );
+    }
+}
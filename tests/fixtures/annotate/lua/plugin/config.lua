@@ -0,0 +1,12 @@
+
+
+-- File: keys.yaml
+
+-- Keybindings:
+-- From keys.yaml in mode n
+vim.keymap.set({ "n" }, "gx", "<CMD>Browse<CR>", { silent = true })
+-- search
+-- From keys.yaml in modes n, v
+vim.keymap.set({ "n", "v" }, "<C-f>", "/", { silent = true, desc = "search" })
+-- From keys.yaml in modes i, c
+vim.keymap.set({ "ia", "ca" }, "teh", "the", {})
\ No newline at end of file
@@ -0,0 +1,14 @@
+
+
+" File: keys.yaml
+
+" Keybindings:
+" From keys.yaml in mode n
+nnoremap <silent> gx <CMD>Browse<CR>
+" search
+" From keys.yaml in modes n, v
+nnoremap <silent> <C-f> /
+vnoremap <silent> <C-f> /
+" From keys.yaml in modes i, c
+inoreabbrev teh the
+cnoreabbrev teh the
\ No newline at end of file
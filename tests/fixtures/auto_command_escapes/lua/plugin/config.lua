@@ -0,0 +1,27 @@
+
+
+-- File: auto_commands.yaml
+
+-- Keybindings:
+vim.api.nvim_create_augroup("auto_commands_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "BufWritePost" }, {
+  group = "auto_commands_yaml",
+  pattern = { "my notes/*.md", "a|b.txt", "it's here.txt" },
+  command = "echo 'saved' | echo \"it's | done\"",
+})
+if vim.fn.has("nvim-0.9.0") == 1 then
+  vim.api.nvim_create_autocmd({ "BufReadPost" }, {
+    group = "auto_commands_yaml",
+    pattern = { "dir with spaces/*" },
+    command = "setlocal readonly | echo 'read | only'",
+  })
+end
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "auto_commands_yaml",
+  pattern = { "x|y z" },
+  callback = function()
+    if vim.v.event.operator == "y" then
+      vim.cmd("echo 'it''s yanked' | echo \"a|b\"")
+    end
+  end,
+})
\ No newline at end of file
@@ -0,0 +1,13 @@
+
+
+" File: auto_commands.yaml
+
+" Keybindings:
+augroup auto_commands_yaml
+autocmd!
+autocmd BufWritePost my\ notes/*.md,a\|b.txt,it's\ here.txt echo 'saved' | echo "it's | done"
+if has('nvim-0.9.0')
+autocmd BufReadPost dir\ with\ spaces/* setlocal readonly | echo 'read | only'
+endif
+autocmd TextYankPost x\|y\ z if v:event.operator is 'y' | execute 'echo ''it''''s yanked'' | echo "a|b"' | endif
+augroup END
\ No newline at end of file
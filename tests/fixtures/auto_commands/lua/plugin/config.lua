@@ -0,0 +1,75 @@
+
+
+-- File: auto_commands.yaml
+
+-- Keybindings:
+vim.api.nvim_create_augroup("auto_commands_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "BufWritePre" }, {
+  group = "auto_commands_yaml",
+  pattern = { "*" },
+  callback = function()
+    if vim.v.event.a == "y" and vim.v.event.b == "x" then
+      vim.cmd("echo 'hi'")
+    end
+  end,
+})
+-- C files
+vim.api.nvim_create_autocmd({ "BufRead", "BufNewFile" }, {
+  group = "auto_commands_yaml",
+  pattern = { "*.c", "*.h", "my file|x" },
+  command = "setlocal cindent | echo 1",
+})
+vim.api.nvim_create_autocmd({ "FileType" }, {
+  group = "auto_commands_yaml",
+  buffer = 0,
+  callback = function()
+    vim.bo.textwidth = 100
+  end,
+})
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "auto_commands_yaml",
+  pattern = { "*" },
+  callback = function()
+    pcall(function()
+      local x = 1
+      vim.highlight.on_yank { timeout = x * 200 }
+    end)
+  end,
+})
+vim.api.nvim_create_autocmd({ "BufWritePost" }, {
+  group = "auto_commands_yaml",
+  pattern = { "src/lib.rs", "src/lib.toml", "src/bin/a.rs", "src/bin/a.toml", "src/bin/b.rs", "src/bin/b.toml", "{x}.md", "{xa}.txt", "{xb}.txt", "{y}/c", "{y}/d" },
+  command = "make",
+})
+vim.api.nvim_create_autocmd({ "BufWritePost" }, {
+  group = "auto_commands_yaml",
+  pattern = { "*" },
+  command = "echo expand('<afile>') '<amatch>'",
+})
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "auto_commands_yaml",
+  pattern = { "*" },
+  callback = function()
+    if vim.v.event.operator == "y" then
+      vim.cmd("echo expand('<afile>') . ' isn''t' | source <afile>")
+    end
+  end,
+})
+vim.api.nvim_create_autocmd({ "BufEnter" }, {
+  group = "auto_commands_yaml",
+  pattern = { "*.md" },
+  desc = "Spell check markdown",
+  command = "setlocal spell",
+})
+vim.api.nvim_create_augroup("shared", { clear = true })
+vim.api.nvim_create_autocmd({ "TermOpen" }, {
+  group = "shared",
+  pattern = { "*" },
+  once = true,
+  nested = true,
+  callback = function()
+    pcall(function()
+      vim.cmd("startinsert")
+    end)
+  end,
+})
\ No newline at end of file
@@ -0,0 +1,29 @@
+
+
+" File: auto_commands.yaml
+
+" Keybindings:
+augroup auto_commands_yaml
+autocmd!
+autocmd BufWritePre * if v:event.a is 'y' && v:event.b is 'x' | execute 'echo ''hi''' | endif
+" C files
+autocmd BufRead,BufNewFile *.c,*.h,my\ file\|x setlocal cindent | echo 1
+autocmd FileType <buffer> lua vim.bo.textwidth = 100
+lua << EOF
+nvim_config_autocmds = nvim_config_autocmds or {}
+nvim_config_autocmds[4] = function()
+  local x = 1
+  vim.highlight.on_yank { timeout = x * 200 }
+end
+EOF
+autocmd TextYankPost * silent! lua nvim_config_autocmds[4]()
+autocmd BufWritePost src/lib.rs,src/lib.toml,src/bin/a.rs,src/bin/a.toml,src/bin/b.rs,src/bin/b.toml,{x}.md,{xa}.txt,{xb}.txt,{y}/c,{y}/d make
+autocmd BufWritePost * echo expand('<afile>') '<amatch>'
+autocmd TextYankPost * if v:event.operator is 'y' | execute 'echo expand(''<afile>'') . '' isn''''t'' | source <afile>' | endif
+" Spell check markdown
+autocmd BufEnter *.md setlocal spell
+augroup END
+augroup shared
+autocmd!
+autocmd TermOpen * ++once ++nested silent! startinsert
+augroup END
\ No newline at end of file
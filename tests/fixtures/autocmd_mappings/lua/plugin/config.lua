@@ -0,0 +1,46 @@
+
+
+-- File: fugitive.yaml
+
+-- Keybindings:
+vim.api.nvim_create_augroup("fugitive_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "FileType" }, {
+  group = "fugitive_yaml",
+  pattern = { "fugitive", "git" },
+  command = "setlocal nonumber",
+})
+vim.api.nvim_create_autocmd({ "FileType" }, {
+  group = "fugitive_yaml",
+  pattern = { "fugitive", "git" },
+  command = "nnoremap <buffer><silent> q :q<CR>",
+})
+vim.api.nvim_create_autocmd({ "FileType" }, {
+  group = "fugitive_yaml",
+  pattern = { "fugitive", "git" },
+  command = "nnoremap <buffer><silent> <C-p> <CMD>Git push<CR>",
+})
+vim.api.nvim_create_autocmd({ "FileType" }, {
+  group = "fugitive_yaml",
+  pattern = { "fugitive", "git" },
+  command = "vnoremap <buffer><silent> <C-p> <CMD>Git push<CR>",
+})
+vim.api.nvim_create_autocmd({ "FileType" }, {
+  group = "fugitive_yaml",
+  pattern = { "fugitive", "git" },
+  command = "nnoremap <buffer><silent> <LEADER>d <CMD>lua (function() print('diff') end)()<CR>",
+})
+vim.api.nvim_create_autocmd({ "FileType" }, {
+  group = "fugitive_yaml",
+  pattern = { "fugitive", "git" },
+  command = "nnoremap <buffer><silent> - <Nop>",
+})
+vim.api.nvim_create_autocmd({ "FileType" }, {
+  group = "fugitive_yaml",
+  pattern = { "fugitive", "git" },
+  command = "nmap <buffer><silent> a <Plug>(fugitive-a)",
+})
+vim.api.nvim_create_autocmd({ "TermOpen" }, {
+  group = "fugitive_yaml",
+  pattern = { "*" },
+  command = "tnoremap <buffer><silent> <Esc> <C-\\><C-n>",
+})
\ No newline at end of file
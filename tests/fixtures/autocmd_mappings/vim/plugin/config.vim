@@ -0,0 +1,16 @@
+
+
+" File: fugitive.yaml
+
+" Keybindings:
+augroup fugitive_yaml
+autocmd!
+autocmd FileType fugitive,git setlocal nonumber
+autocmd FileType fugitive,git nnoremap <buffer><silent> q :q<CR>
+autocmd FileType fugitive,git nnoremap <buffer><silent> <C-p> <CMD>Git push<CR>
+autocmd FileType fugitive,git vnoremap <buffer><silent> <C-p> <CMD>Git push<CR>
+autocmd FileType fugitive,git nnoremap <buffer><silent> <LEADER>d <CMD>lua (function() print('diff') end)()<CR>
+autocmd FileType fugitive,git nnoremap <buffer><silent> - <Nop>
+autocmd FileType fugitive,git nmap <buffer><silent> a <Plug>(fugitive-a)
+autocmd TermOpen * tnoremap <buffer><silent> <Esc> <C-\><C-n>
+augroup END
\ No newline at end of file
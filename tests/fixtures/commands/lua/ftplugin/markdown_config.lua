@@ -0,0 +1 @@
+vim.keymap.set({ "ia" }, "--", "—", { buffer = true })
\ No newline at end of file
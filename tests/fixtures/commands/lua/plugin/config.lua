@@ -0,0 +1,22 @@
+
+
+-- File: commands.toml
+
+-- Keybindings:
+vim.keymap.set({ "ca" }, "Qa", "qa", {})
+vim.keymap.set({ "ca" }, "Wq", "getcmdtype() ==# ':' && getcmdline() ==# 'Wq' ? 'wq' : 'Wq'", { expr = true })
+vim.keymap.set({ "ca" }, "lg", "getcmdtype() ==# ':' && getcmdpos() == 3 ? 'lgrep' : 'lg'", { expr = true })
+vim.keymap.set({ "ca" }, "W", "w", { remap = true })
+vim.keymap.set({ "ia" }, "teh", "the", {})
+vim.api.nvim_create_user_command("Bclose", "<count>bdelete", { count = 0, addr = "loaded_buffers" })
+vim.api.nvim_create_user_command("Grep", "silent grep <args> | copen", { nargs = "+", complete = "file" })
+vim.api.nvim_create_user_command("Reload", function(opts)
+  for name in pairs(package.loaded) do
+    package.loaded[name] = nil
+  end
+  print(opts.args)
+end, { nargs = "?", range = true, bang = true })
+vim.api.nvim_create_user_command("Sort", "<line1>,<line2>sort", { range = "%" })
+vim.api.nvim_create_user_command("Tabs", function(opts)
+  print(opts.count)
+end, { count = true, addr = "tab" })
\ No newline at end of file
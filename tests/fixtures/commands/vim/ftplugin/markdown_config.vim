@@ -0,0 +1 @@
+inoreabbrev <buffer> -- —
\ No newline at end of file
@@ -0,0 +1,30 @@
+
+
+" File: commands.toml
+
+" Keybindings:
+cnoreabbrev Qa qa
+cnoreabbrev <expr> Wq getcmdtype() ==# ':' && getcmdline() ==# 'Wq' ? 'wq' : 'Wq'
+cnoreabbrev <expr> lg getcmdtype() ==# ':' && getcmdpos() == 3 ? 'lgrep' : 'lg'
+cabbrev W w
+inoreabbrev teh the
+command! -count=0 -addr=loaded_buffers Bclose <count>bdelete
+command! -nargs=+ -complete=file Grep silent grep <args> | copen
+lua << EOF
+nvim_config_commands = nvim_config_commands or {}
+nvim_config_commands["Reload"] = function(opts)
+  for name in pairs(package.loaded) do
+    package.loaded[name] = nil
+  end
+  print(opts.args)
+end
+EOF
+command! -nargs=? -range -bang Reload lua nvim_config_commands["Reload"]({ args = <q-args>, fargs = { <f-args> }, bang = <q-bang> == "!", line1 = <line1>, line2 = <line2>, range = <range>, count = <count>, mods = <q-mods> })
+command! -range=% Sort <line1>,<line2>sort
+lua << EOF
+nvim_config_commands = nvim_config_commands or {}
+nvim_config_commands["Tabs"] = function(opts)
+  print(opts.count)
+end
+EOF
+command! -count -addr=tab Tabs lua nvim_config_commands["Tabs"]({ args = <q-args>, fargs = { <f-args> }, bang = <q-bang> == "!", line1 = <line1>, line2 = <line2>, range = <range>, count = <count>, mods = <q-mods> })
\ No newline at end of file
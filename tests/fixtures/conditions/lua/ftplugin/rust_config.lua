@@ -0,0 +1,3 @@
+if vim.fn.has("nvim-0.10.0") == 0 then
+  vim.cmd("setlocal expandtab")
+end
\ No newline at end of file
@@ -0,0 +1,50 @@
+
+
+-- File: 00-always.yaml
+
+-- Keybindings:
+vim.api.nvim_create_augroup("00_always_yaml", { clear = true })
+if vim.fn.has("nvim-0.10.0") == 1 then
+  vim.api.nvim_create_autocmd({ "BufEnter" }, {
+    group = "00_always_yaml",
+    pattern = { "*" },
+    command = "echo 'new'",
+  })
+end
+if vim.fn.has("nvim-0.9.0") == 1 and vim.fn.has("nvim-0.9.1") == 0 and (vim.fn.has("nvim-0.9.1") == 0 or vim.fn.has("nvim-0.9.2") == 1) then
+  vim.api.nvim_create_autocmd({ "BufLeave" }, {
+    group = "00_always_yaml",
+    pattern = { "*" },
+    command = "echo 'exactly 0.9'",
+  })
+end
+vim.cmd("set number")
+
+
+-- File: 10-runtime.yaml
+
+-- Keybindings:
+if vim.fn.has("nvim-0.10.0") == 0 then
+  vim.keymap.set({ "n" }, "x", "y", { silent = true })
+  vim.cmd("set nonumber")
+end
+
+
+-- File: 20-never.yaml
+-- Skipped, `os == "plan9"` does not hold
+
+
+-- File: 30-features.yaml
+
+-- Keybindings:
+if vim.fn.has("termguicolors") == 1 then
+  vim.api.nvim_create_augroup("30_features_yaml", { clear = true })
+  if vim.fn.has("clipboard") == 0 then
+    vim.api.nvim_create_autocmd({ "VimEnter" }, {
+      group = "30_features_yaml",
+      pattern = { "*" },
+      command = "echo 'no clipboard'",
+    })
+  end
+  vim.cmd("set termguicolors")
+end
\ No newline at end of file
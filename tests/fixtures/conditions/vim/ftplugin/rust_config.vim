@@ -0,0 +1,3 @@
+if !has('nvim-0.10.0')
+setlocal expandtab
+endif
\ No newline at end of file
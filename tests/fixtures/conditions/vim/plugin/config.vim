@@ -0,0 +1,42 @@
+
+
+" File: 00-always.yaml
+
+" Keybindings:
+augroup 00_always_yaml
+autocmd!
+if has('nvim-0.10.0')
+autocmd BufEnter * echo 'new'
+endif
+if has('nvim-0.9.0') && !has('nvim-0.9.1') && (!has('nvim-0.9.1') || has('nvim-0.9.2'))
+autocmd BufLeave * echo 'exactly 0.9'
+endif
+augroup END
+set number
+
+
+" File: 10-runtime.yaml
+
+" Keybindings:
+if !has('nvim-0.10.0')
+nnoremap <silent> x y
+set nonumber
+endif
+
+
+" File: 20-never.yaml
+" Skipped, `os == "plan9"` does not hold
+
+
+" File: 30-features.yaml
+
+" Keybindings:
+if has('termguicolors')
+augroup 30_features_yaml
+autocmd!
+if !has('clipboard')
+autocmd VimEnter * echo 'no clipboard'
+endif
+augroup END
+set termguicolors
+endif
\ No newline at end of file
@@ -0,0 +1,2 @@
+" Written on Windows
+set ruler
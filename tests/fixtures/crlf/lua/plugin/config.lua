@@ -0,0 +1,20 @@
+
+
+-- File: windows.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-s>", ":w<CR>", { silent = true })
+vim.api.nvim_create_augroup("windows_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "windows_yaml",
+  pattern = { "*" },
+  callback = function()
+    local x = 1
+    vim.highlight.on_yank { timeout = x * 200 }
+  end,
+})
+-- Source: snippets/windows.vim
+vim.cmd([[
+" Written on Windows
+set ruler
+]])
\ No newline at end of file
@@ -0,0 +1,20 @@
+
+
+" File: windows.yaml
+
+" Keybindings:
+nnoremap <silent> <C-s> :w<CR>
+augroup windows_yaml
+autocmd!
+lua << EOF
+nvim_config_autocmds = nvim_config_autocmds or {}
+nvim_config_autocmds[0] = function()
+  local x = 1
+  vim.highlight.on_yank { timeout = x * 200 }
+end
+EOF
+autocmd TextYankPost * lua nvim_config_autocmds[0]()
+augroup END
+" Source: snippets/windows.vim
+" Written on Windows
+set ruler
\ No newline at end of file
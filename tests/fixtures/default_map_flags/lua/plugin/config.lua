@@ -0,0 +1,26 @@
+
+
+-- File: buffer.toml
+
+-- Keybindings:
+vim.keymap.set({ "o" }, "ae", ":<C-u>normal! ggVG<CR>", { buffer = true, silent = true })
+vim.keymap.set({ "n" }, "Q", "<Nop>", { silent = true })
+
+
+-- File: keys.yaml
+
+-- Keybindings:
+vim.keymap.set({ "v" }, "<LEADER>y", "\"+y", { silent = true })
+vim.keymap.set({ "n" }, "<C-s>", "<CMD>write<CR>", { silent = true })
+vim.keymap.set({ "i" }, "jk", "<ESC>", {})
+-- find
+vim.keymap.set({ "n" }, "<LEADER>f", "Telescope find_files", { silent = true, desc = "find" })
+vim.keymap.set({ "n" }, "<LEADER>g", "Telescope live_grep", { silent = true, desc = "find" })
+
+
+-- File: quickfix.json
+
+-- Keybindings:
+-- quickfix
+vim.keymap.set({ "n" }, "<C-j>", "<CMD>cnext<CR>", { silent = true, desc = "quickfix" })
+vim.keymap.set({ "n" }, "<C-k>", "<CMD>cprevious<CR>", { silent = true, desc = "quickfix" })
\ No newline at end of file
@@ -0,0 +1,26 @@
+
+
+" File: buffer.toml
+
+" Keybindings:
+onoremap <buffer><silent> ae :<C-u>normal! ggVG<CR>
+nnoremap <silent> Q <Nop>
+
+
+" File: keys.yaml
+
+" Keybindings:
+vnoremap <silent> <LEADER>y "+y
+nnoremap <silent> <C-s> <CMD>write<CR>
+inoremap jk <ESC>
+" find
+nnoremap <silent> <LEADER>f Telescope find_files
+nnoremap <silent> <LEADER>g Telescope live_grep
+
+
+" File: quickfix.json
+
+" Keybindings:
+" quickfix
+nnoremap <silent> <C-j> <CMD>cnext<CR>
+nnoremap <silent> <C-k> <CMD>cprevious<CR>
\ No newline at end of file
@@ -0,0 +1,2 @@
+vim.opt_local.makeprg = "make -C build"
+vim.opt_local.errorformat = "%f:%l:%c: %trror: %m,%f:%l:%c: %tarning: %m,%-G%.%#"
\ No newline at end of file
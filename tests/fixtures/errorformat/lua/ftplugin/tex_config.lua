@@ -0,0 +1 @@
+vim.opt_local.errorformat = "%E! LaTeX %trror: %m,%Z\\\\%.%#,%-G|%.%#"
\ No newline at end of file
@@ -0,0 +1,5 @@
+
+
+-- File: compilers.yaml
+
+-- Keybindings:
\ No newline at end of file
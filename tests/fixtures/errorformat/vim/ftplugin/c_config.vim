@@ -0,0 +1,2 @@
+setlocal makeprg=make\ -C\ build
+setlocal errorformat=%f:%l:%c:\ %trror:\ %m,%f:%l:%c:\ %tarning:\ %m,%-G%.%#
\ No newline at end of file
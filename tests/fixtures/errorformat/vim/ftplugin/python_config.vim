@@ -0,0 +1,2 @@
+setlocal makeprg=python3\ -m\ pylint\ --output-format=parseable\ %
+setlocal errorformat=%A%f:%l:\ [%t%n(%o)\\,\ %.%#]\ %m,%A\ \ File\ \"%f\"\\,\ line\ %l\\,\ %m,%f:%l:\ [%t]\ %m\\,\ in\ \"%s\",%-G%.%#
\ No newline at end of file
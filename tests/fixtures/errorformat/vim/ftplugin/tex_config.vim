@@ -0,0 +1 @@
+setlocal errorformat=%E!\ LaTeX\ %trror:\ %m,%Z\\\\%.%#,%-G\|%.%#
\ No newline at end of file
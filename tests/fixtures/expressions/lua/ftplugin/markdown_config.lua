@@ -0,0 +1 @@
+vim.opt_local.textwidth = vim.fn.eval("&columns > 100 ? 100 : 80")
\ No newline at end of file
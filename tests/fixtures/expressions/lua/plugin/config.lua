@@ -0,0 +1,10 @@
+
+
+-- File: options.yaml
+
+-- Keybindings:
+vim.opt.tabstop = 4
+vim.opt.scrolloff = vim.fn.eval("&lines / 4")
+vim.opt.shiftwidth = vim.fn.eval("&tabstop")
+vim.g.config_dir = vim.fn.eval("stdpath('config')")
+vim.g.title = "nvim"
\ No newline at end of file
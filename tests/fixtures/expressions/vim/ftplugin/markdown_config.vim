@@ -0,0 +1 @@
+let &l:textwidth=&columns > 100 ? 100 : 80
\ No newline at end of file
@@ -0,0 +1,10 @@
+
+
+" File: options.yaml
+
+" Keybindings:
+set tabstop=4
+let &scrolloff=&lines / 4
+let &shiftwidth=&tabstop
+let g:config_dir=stdpath('config')
+let g:title='nvim'
\ No newline at end of file
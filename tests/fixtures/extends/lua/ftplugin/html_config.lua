@@ -0,0 +1 @@
+vim.cmd("setlocal spell")
\ No newline at end of file
@@ -0,0 +1,47 @@
+
+
+-- File: 00-main.yaml
+-- The base with more
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+vim.keymap.set({ "n" }, "<C-l>", "<C-w>l", { silent = true })
+vim.keymap.set({ "n" }, "gg", "gg", { silent = true })
+vim.api.nvim_create_augroup("00_main_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "00_main_yaml",
+  pattern = { "*" },
+  callback = function()
+    vim.highlight.on_yank()
+  end,
+})
+vim.cmd("set number")
+vim.cmd("set relativenumber")
+vim.opt.tabstop = 4
+vim.opt.shiftwidth = 4
+
+
+-- File: _local.yaml
+-- Not extended by any file, so used like any other
+
+-- Keybindings:
+vim.cmd("set cursorline")
+
+
+-- File: lang/html.yaml
+-- Shared by every file extending it
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+vim.keymap.set({ "n" }, "gg", "G", { silent = true })
+vim.api.nvim_create_augroup("lang_html_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "lang_html_yaml",
+  pattern = { "*" },
+  callback = function()
+    vim.highlight.on_yank()
+  end,
+})
+vim.cmd("set number")
+vim.opt.tabstop = 2
+vim.opt.shiftwidth = 4
\ No newline at end of file
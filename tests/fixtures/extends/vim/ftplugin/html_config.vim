@@ -0,0 +1 @@
+setlocal spell
\ No newline at end of file
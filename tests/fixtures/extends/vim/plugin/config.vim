@@ -0,0 +1,39 @@
+
+
+" File: 00-main.yaml
+" The base with more
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+nnoremap <silent> <C-l> <C-w>l
+nnoremap <silent> gg gg
+augroup 00_main_yaml
+autocmd!
+autocmd TextYankPost * lua vim.highlight.on_yank()
+augroup END
+set number
+set relativenumber
+set tabstop=4
+set shiftwidth=4
+
+
+" File: _local.yaml
+" Not extended by any file, so used like any other
+
+" Keybindings:
+set cursorline
+
+
+" File: lang/html.yaml
+" Shared by every file extending it
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+nnoremap <silent> gg G
+augroup lang_html_yaml
+autocmd!
+autocmd TextYankPost * lua vim.highlight.on_yank()
+augroup END
+set number
+set tabstop=2
+set shiftwidth=4
\ No newline at end of file
@@ -0,0 +1,33 @@
+
+
+-- File: 00-keys.yaml
+-- Consecutive mappings are aligned with --format
+
+-- Keybindings:
+vim.keymap.set({ "i" },      "jk",     "<Esc>", { silent = true })
+vim.keymap.set({ "n" },      "<C-h>",  "<C-w>h", { silent = true })
+vim.keymap.set({ "n" },      "<C-l>",  "<C-w>l", { silent = true })
+vim.keymap.set({ "n" },      "Y",      "y$", { silent = true, script = true, unique = true })
+vim.keymap.set({ "n" },      "bbbbbb", "y", { silent = true, unique = true })
+vim.keymap.set({ "n" },      "gg",     "G", { silent = true })
+vim.keymap.set({ "n", "v" }, "<C-s>",  ":write<CR>", { buffer = true, silent = true })
+vim.keymap.set({ "n", "v" }, "x",      "\"_x", { buffer = true, silent = true })
+-- find
+vim.keymap.set({ "n" }, "<LEADER>fb", "<CMD>Telescope buffers<CR>", {})
+vim.keymap.set({ "n" }, "<LEADER>ff", "<CMD>Telescope find_files<CR>", { silent = true })
+-- grep
+vim.keymap.set({ "n" }, "<LEADER>fg", "<CMD>Telescope live_grep<CR>", { silent = true, desc = "grep" })
+pcall(function()
+  require("which-key").add({
+    { "<LEADER>f", group = "find", mode = { "n" } },
+  })
+end)
+
+
+-- File: 10-runtime.yaml
+
+-- Keybindings:
+if vim.fn.has("nvim-0.10.0") == 0 then
+  vim.keymap.set({ "n" }, "<leader>q", ":quit<CR>", { silent = true })
+  vim.keymap.set({ "n" }, "Q",         "gq", { silent = true })
+end
\ No newline at end of file
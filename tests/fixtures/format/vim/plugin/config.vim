@@ -0,0 +1,30 @@
+
+
+" File: 00-keys.yaml
+" Consecutive mappings are aligned with --format
+
+" Keybindings:
+inoremap <silent>                 jk     <Esc>
+nnoremap <silent>                 <C-h>  <C-w>h
+nnoremap <silent>                 <C-l>  <C-w>l
+nnoremap <silent><script><unique> Y      y$
+nnoremap <silent><unique>         bbbbbb y
+nnoremap <silent>                 gg     G
+nnoremap <buffer><silent>         <C-s>  :write<CR>
+vnoremap <buffer><silent>         <C-s>  :write<CR>
+nnoremap <buffer><silent>         x      "_x
+vnoremap <buffer><silent>         x      "_x
+" find
+nnoremap          <LEADER>fb <CMD>Telescope buffers<CR>
+nnoremap <silent> <LEADER>ff <CMD>Telescope find_files<CR>
+" grep
+nnoremap <silent> <LEADER>fg <CMD>Telescope live_grep<CR>
+
+
+" File: 10-runtime.yaml
+
+" Keybindings:
+if !has('nvim-0.10.0')
+nnoremap <silent> <leader>q :quit<CR>
+nnoremap <silent> Q         gq
+endif
\ No newline at end of file
@@ -0,0 +1,33 @@
+
+
+-- File: a.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+vim.cmd("set number")
+vim.opt.tabstop = 8
+vim.opt.shiftwidth = 2.5
+vim.g.path = "a/b"
+vim.g.unicode = "é😀"
+
+
+-- File: b.json
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+vim.cmd("set number")
+vim.opt.tabstop = 8
+vim.opt.shiftwidth = 2.5
+vim.g.path = "a/b"
+vim.g.unicode = "é😀"
+
+
+-- File: c.toml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+vim.cmd("set number")
+vim.opt.tabstop = 8
+vim.opt.shiftwidth = 2.5
+vim.g.path = "a/b"
+vim.g.unicode = "é😀"
\ No newline at end of file
@@ -0,0 +1,33 @@
+
+
+" File: a.yaml
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+set number
+set tabstop=8
+set shiftwidth=2.5
+let g:path='a/b'
+let g:unicode='é😀'
+
+
+" File: b.json
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+set number
+set tabstop=8
+set shiftwidth=2.5
+let g:path='a/b'
+let g:unicode='é😀'
+
+
+" File: c.toml
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+set number
+set tabstop=8
+set shiftwidth=2.5
+let g:path='a/b'
+let g:unicode='é😀'
\ No newline at end of file
@@ -0,0 +1,28 @@
+
+
+-- File: keys.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "ga", "ga", { silent = true })
+vim.keymap.set({ "n" }, "gg", "G", { silent = true })
+-- <LEADER><C-g>
+vim.keymap.set({ "n" }, "<LEADER><C-g>g", "Git", { silent = true })
+-- <LEADER>f
+vim.keymap.set({ "n" }, "<LEADER>ff", "Telescope find_files", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>fg", "Telescope live_grep", { silent = true })
+-- <LEADER>g
+vim.keymap.set({ "n" }, "<LEADER>gb", "Git blame", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>gs", "Git status", { silent = true })
+-- <LEADER>q
+vim.keymap.set({ "n" }, "<LEADER>q", "quit", { silent = true })
+-- <LEADER>w
+vim.keymap.set({ "n" }, "<LEADER>w", "update", { silent = true })
+-- git
+-- <LEADER>g
+vim.keymap.set({ "n" }, "<LEADER>gc", "<CMD>Git commit<CR>", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>gp", "<CMD>Git push<CR>", { silent = true })
+pcall(function()
+  require("which-key").add({
+    { "<LEADER>g", group = "git", mode = { "n" } },
+  })
+end)
\ No newline at end of file
@@ -0,0 +1,23 @@
+
+
+" File: keys.yaml
+
+" Keybindings:
+nnoremap <silent> ga ga
+nnoremap <silent> gg G
+" <LEADER><C-g>
+nnoremap <silent> <LEADER><C-g>g Git
+" <LEADER>f
+nnoremap <silent> <LEADER>ff Telescope find_files
+nnoremap <silent> <LEADER>fg Telescope live_grep
+" <LEADER>g
+nnoremap <silent> <LEADER>gb Git blame
+nnoremap <silent> <LEADER>gs Git status
+" <LEADER>q
+nnoremap <silent> <LEADER>q quit
+" <LEADER>w
+nnoremap <silent> <LEADER>w update
+" git
+" <LEADER>g
+nnoremap <silent> <LEADER>gc <CMD>Git commit<CR>
+nnoremap <silent> <LEADER>gp <CMD>Git push<CR>
\ No newline at end of file
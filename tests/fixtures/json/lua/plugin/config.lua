@@ -0,0 +1,9 @@
+
+
+-- File: settings.json
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+vim.cmd("set number")
+vim.opt.tabstop = 8
+vim.g.json = "yes"
\ No newline at end of file
@@ -0,0 +1,9 @@
+
+
+" File: settings.json
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+set number
+set tabstop=8
+let g:json='yes'
\ No newline at end of file
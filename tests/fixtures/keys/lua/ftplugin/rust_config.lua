@@ -0,0 +1,9 @@
+vim.keymap.set({ "n" }, "K", "<Nop>", { silent = true })
+-- Rust
+vim.keymap.set({ "i", "n" }, "<C-r>", "cargo run", { silent = true, desc = "Rust" })
+vim.cmd("setlocal expandtab")
+vim.opt_local.shiftwidth = 2
+vim.opt_local.spell = true
+vim.opt_local.wrap = false
+vim.b.format_on_save = true
+vim.w.ruler = 100
\ No newline at end of file
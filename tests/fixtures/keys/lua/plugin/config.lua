@@ -0,0 +1,69 @@
+
+
+-- File: keys.yaml
+-- Mappings in every flavour
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "g<Space>g", "<C-w>w", { silent = true })
+vim.keymap.set({ "n" }, "gg", "G", { silent = true })
+vim.keymap.set({ "n", "v" }, "<C-s>", "<CMD>write<CR>", { silent = true })
+-- tab
+vim.keymap.set({ "n" }, "<C-t>", function() print('tab') end, { silent = true, desc = "tab" })
+vim.keymap.set({ "v" }, "<C-t>", "<CMD>tabnew<CR>", { silent = true, desc = "tab" })
+vim.keymap.set({ "n" }, "Y", "y$", { silent = true, remap = true })
+vim.keymap.set({ "n" }, "j", "v:count ? 'j' : 'gj'", { silent = true, expr = true })
+vim.keymap.set({ "n" }, "Q", ":echo 'quiet'<CR>", { buffer = true, silent = true })
+vim.keymap.set({ "n" }, "q", ":echo 'a|b'<CR>", { buffer = true })
+vim.keymap.set({ "n" }, "u", ":echo 'unique'<CR>", { buffer = true, nowait = true, script = true, unique = true })
+vim.keymap.set({ "c" }, "<C-a>", "<Home>", {})
+vim.keymap.set({ "n" }, "<LEADER>g", ":Git<CR>", { nowait = true, silent = true })
+vim.keymap.set({ "n", "v" }, "S", "<Nop>", { silent = true })
+vim.keymap.set({ "n", "v" }, "s", "<Nop>", { silent = true })
+-- find
+vim.keymap.set({ "n" }, "<LEADER>fb", "Telescope buffers", {})
+vim.keymap.set({ "n" }, "<LEADER>ff", "Telescope find_files", { silent = true })
+-- grep
+vim.keymap.set({ "n" }, "<LEADER>fg", "Telescope live_grep", { silent = true, desc = "grep" })
+vim.keymap.set({ "n" }, "<LEADER>fh", require('telescope.builtin').help_tags, { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>x", ":q<CR>", { silent = true, desc = "find" })
+pcall(function()
+  require("which-key").add({
+    { "<LEADER>f", group = "find", mode = { "n" } },
+  })
+end)
+-- find_more
+vim.keymap.set({ "n" }, "<LEADER>q", "<Nop>", { silent = true, desc = "find_more" })
+-- menu
+-- branches
+vim.keymap.set({ "n" }, "<LEADER>gbc", "<CMD>Git checkout<CR>", { silent = true })
+-- new branch
+vim.keymap.set({ "n" }, "<LEADER>gbn", "<CMD>Git switch -c<CR>", { silent = true, desc = "new branch" })
+vim.keymap.set({ "n" }, "<LEADER>gbrd", "<CMD>Git push --delete<CR>", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>gs", "<CMD>Git status<CR>", { silent = true })
+pcall(function()
+  require("which-key").add({
+    { "<LEADER>g", group = "menu", mode = { "n" } },
+    { "<LEADER>gb", group = "branches", mode = { "n" } },
+  })
+end)
+-- move
+-- next
+vim.keymap.set({ "i", "v" }, "<C-j>", "<CMD>m .+1<CR>", { silent = true, desc = "next" })
+vim.keymap.set({ "n" }, "<C-j>", "<CMD>cnext<CR>", { silent = true, desc = "next" })
+vim.cmd("set number")
+vim.cmd("set relativenumber")
+vim.opt.tabstop = 4
+vim.opt.wrap = false
+vim.opt.scrolloff = 2.5
+vim.opt.listchars = { "tab:>", "trail:-" }
+vim.opt.spelllang = "en"
+vim.opt.fillchars = { eob = " ", fold = "-" }
+vim.opt.showbreak = "> "
+vim.opt.foldtext = "printf(\"%s|\", getline(v:foldstart))"
+vim.g.mapleader = " "
+vim.g.plain = 1
+vim.g.greeting = "hello world"
+vim.g.markdown_fenced_languages = { "rust", "bash=sh" }
+vim.g.lsp_settings = { ["rust-analyzer"] = { checkOnSave = 1, features = { "all" } }, timeout = 2.5 }
+vim.b.answer = 42
+vim.w.ratio = 10000000000.0
\ No newline at end of file
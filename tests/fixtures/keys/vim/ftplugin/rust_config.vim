@@ -0,0 +1,10 @@
+nnoremap <silent> K <Nop>
+" Rust
+inoremap <silent> <C-r> cargo run
+nnoremap <silent> <C-r> cargo run
+setlocal expandtab
+setlocal shiftwidth=2
+setlocal spell
+setlocal nowrap
+let b:format_on_save=v:true
+let w:ruler=100
\ No newline at end of file
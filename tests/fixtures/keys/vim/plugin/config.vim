@@ -0,0 +1,62 @@
+
+
+" File: keys.yaml
+" Mappings in every flavour
+
+" Keybindings:
+nnoremap <silent> g<Space>g <C-w>w
+nnoremap <silent> gg G
+nnoremap <silent> <C-s> <CMD>write<CR>
+vnoremap <silent> <C-s> <CMD>write<CR>
+" tab
+nnoremap <silent> <C-t> <CMD>lua (function() print('tab') end)()<CR>
+vnoremap <silent> <C-t> <CMD>tabnew<CR>
+nmap <silent> Y y$
+nnoremap <silent><expr> j v:count ? 'j' : 'gj'
+nnoremap <buffer><silent> Q :echo 'quiet'<CR>
+nnoremap <buffer> q :echo 'a\|b'<CR>
+nnoremap <buffer><nowait><script><unique> u :echo 'unique'<CR>
+cnoremap <C-a> <Home>
+nnoremap <nowait><silent> <LEADER>g :Git<CR>
+nnoremap <silent> S <Nop>
+vnoremap <silent> S <Nop>
+nnoremap <silent> s <Nop>
+vnoremap <silent> s <Nop>
+" find
+nnoremap <LEADER>fb Telescope buffers
+nnoremap <silent> <LEADER>ff Telescope find_files
+" grep
+nnoremap <silent> <LEADER>fg Telescope live_grep
+nnoremap <silent> <LEADER>fh <CMD>lua (require('telescope.builtin').help_tags)()<CR>
+nnoremap <silent> <LEADER>x :q<CR>
+" find_more
+nnoremap <silent> <LEADER>q <Nop>
+" menu
+" branches
+nnoremap <silent> <LEADER>gbc <CMD>Git checkout<CR>
+" new branch
+nnoremap <silent> <LEADER>gbn <CMD>Git switch -c<CR>
+nnoremap <silent> <LEADER>gbrd <CMD>Git push --delete<CR>
+nnoremap <silent> <LEADER>gs <CMD>Git status<CR>
+" move
+" next
+inoremap <silent> <C-j> <CMD>m .+1<CR>
+vnoremap <silent> <C-j> <CMD>m .+1<CR>
+nnoremap <silent> <C-j> <CMD>cnext<CR>
+set number
+set relativenumber
+set tabstop=4
+set nowrap
+set scrolloff=2.5
+set listchars=tab:>,trail:-
+set spelllang=en
+set fillchars=eob:\ ,fold:-
+set showbreak=>\ 
+set foldtext=printf(\"%s\|\",\ getline(v:foldstart))
+let g:mapleader=' '
+let plain=1
+let greeting='hello world'
+let g:markdown_fenced_languages=['rust', 'bash=sh']
+let g:lsp_settings={'rust-analyzer': {'checkOnSave': 1, 'features': ['all']}, 'timeout': 2.5}
+let b:answer=42
+let w:ratio=10000000000.0
\ No newline at end of file
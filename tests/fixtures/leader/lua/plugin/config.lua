@@ -0,0 +1,23 @@
+-- Leader keys, set before any mapping:
+vim.g.mapleader = " "
+vim.g.maplocalleader = ","
+if vim.fn.has("mac") == 1 then
+  vim.g.maplocalleader = "\\"
+end
+
+
+-- File: 10-keys.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<LEADER>w", "update", { silent = true })
+vim.keymap.set({ "n" }, "<LOCALLEADER>r", "make run", { silent = true })
+
+
+-- File: 20-leader.yaml
+
+-- Keybindings:
+
+
+-- File: 30-mac.yaml
+
+-- Keybindings:
\ No newline at end of file
@@ -0,0 +1,23 @@
+" Leader keys, set before any mapping:
+let g:mapleader=' '
+let g:maplocalleader=','
+if has('mac')
+let g:maplocalleader='\'
+endif
+
+
+" File: 10-keys.yaml
+
+" Keybindings:
+nnoremap <silent> <LEADER>w update
+nnoremap <silent> <LOCALLEADER>r make run
+
+
+" File: 20-leader.yaml
+
+" Keybindings:
+
+
+" File: 30-mac.yaml
+
+" Keybindings:
\ No newline at end of file
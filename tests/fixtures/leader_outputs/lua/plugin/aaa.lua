@@ -0,0 +1,10 @@
+-- Leader keys, set before any mapping:
+vim.g.mapleader = " "
+vim.g.maplocalleader = ","
+
+
+-- File: 20_tools.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "gt", "make", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>t", "make test", { silent = true })
\ No newline at end of file
@@ -0,0 +1,6 @@
+
+
+-- File: 25_plain.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "gb", "make", { silent = true })
\ No newline at end of file
@@ -0,0 +1,9 @@
+-- Leader keys, set before any mapping:
+vim.g.mapleader = " "
+vim.g.maplocalleader = ","
+
+
+-- File: 10_leader.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<LEADER>w", "update", { silent = true })
\ No newline at end of file
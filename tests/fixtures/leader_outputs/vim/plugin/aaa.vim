@@ -0,0 +1,10 @@
+" Leader keys, set before any mapping:
+let g:mapleader=' '
+let g:maplocalleader=','
+
+
+" File: 20_tools.yaml
+
+" Keybindings:
+nnoremap <silent> gt make
+nnoremap <silent> <LEADER>t make test
\ No newline at end of file
@@ -0,0 +1,6 @@
+
+
+" File: 25_plain.yaml
+
+" Keybindings:
+nnoremap <silent> gb make
\ No newline at end of file
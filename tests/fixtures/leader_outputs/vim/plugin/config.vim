@@ -0,0 +1,15 @@
+" Leader keys, set before any mapping:
+let g:mapleader=' '
+let g:maplocalleader=','
+
+
+" File: 10_leader.yaml
+
+" Keybindings:
+nnoremap <silent> <LEADER>w update
+
+
+" File: 30_vim.yaml
+
+" Keybindings:
+nnoremap <silent> <LOCALLEADER>r make run
\ No newline at end of file
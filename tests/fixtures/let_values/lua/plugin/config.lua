@@ -0,0 +1,13 @@
+
+
+-- File: variables.yaml
+
+-- Keybindings:
+vim.g.enabled = true
+vim.g.disabled = false
+vim.g.settings = { format_on_save = true, lint = false, tools = { true, 1 } }
+vim.g.quote = "say \"hi\""
+vim.g.winpath = "C:\\new\\table"
+vim.g.apostrophe = "it's"
+vim.g.escape = vim.fn.eval("\"\\<Esc>\"")
+vim.g.nested = { paths = { "C:\\tmp", "a\"b" } }
\ No newline at end of file
@@ -0,0 +1,13 @@
+
+
+" File: variables.yaml
+
+" Keybindings:
+let g:enabled=v:true
+let g:disabled=v:false
+let g:settings={'format_on_save': v:true, 'lint': v:false, 'tools': [v:true, 1]}
+let g:quote='say "hi"'
+let g:winpath='C:\new\table'
+let g:apostrophe='it''s'
+let g:escape="\<Esc>"
+let g:nested={'paths': ['C:\tmp', 'a"b']}
\ No newline at end of file
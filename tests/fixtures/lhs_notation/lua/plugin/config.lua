@@ -0,0 +1,14 @@
+
+
+-- File: keys.yaml
+
+-- Keybindings:
+vim.keymap.set({ "i" }, "<S-Tab>",           "<C-d>", { silent = true })
+vim.keymap.set({ "n" }, "<C-a><Space><C-b>", ":echo 'spaced'<CR>", { silent = true })
+vim.keymap.set({ "n" }, "<",                 "<<", { silent = true })
+vim.keymap.set({ "n" }, "<Bslash>",          ":echo 'backslash'<CR>", { silent = true })
+vim.keymap.set({ "n" }, "<C-Space>",         "<C-x><C-o>", { silent = true })
+vim.keymap.set({ "n" }, "<C-w><lt>",         "<C-w>5<lt>", { silent = true })
+vim.keymap.set({ "n" }, "<lt>",              "<<", { silent = true })
+vim.keymap.set({ "n" }, "<lt>a>",            ":echo 'literal'<CR>", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER><lt>",      ":echo 'leader lt'<CR>", { silent = true })
\ No newline at end of file
@@ -0,0 +1,14 @@
+
+
+" File: keys.yaml
+
+" Keybindings:
+inoremap <silent> <S-Tab>           <C-d>
+nnoremap <silent> <C-a><Space><C-b> :echo 'spaced'<CR>
+nnoremap <silent> <                 <<
+nnoremap <silent> <Bslash>          :echo 'backslash'<CR>
+nnoremap <silent> <C-Space>         <C-x><C-o>
+nnoremap <silent> <C-w><lt>         <C-w>5<lt>
+nnoremap <silent> <lt>              <<
+nnoremap <silent> <lt>a>            :echo 'literal'<CR>
+nnoremap <silent> <LEADER><lt>      :echo 'leader lt'<CR>
\ No newline at end of file
@@ -0,0 +1,15 @@
+
+
+-- File: base.yaml
+
+-- Keybindings:
+vim.keymap.set({ "i", "n" }, "<C-s>", "<CMD>write<CR>", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>w", ":w<CR>", { silent = true })
+vim.api.nvim_create_augroup("base_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "BufRead", "BufNewFile" }, {
+  group = "base_yaml",
+  pattern = { "*" },
+  command = "setlocal spell",
+})
+vim.opt.tabstop = 4
+vim.g.mapleader = " "
\ No newline at end of file
@@ -0,0 +1 @@
+setlocal expandtab
\ No newline at end of file
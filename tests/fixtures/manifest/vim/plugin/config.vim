@@ -0,0 +1,14 @@
+
+
+" File: base.yaml
+
+" Keybindings:
+inoremap <silent> <C-s> <CMD>write<CR>
+nnoremap <silent> <C-s> <CMD>write<CR>
+nnoremap <silent> <LEADER>w :w<CR>
+augroup base_yaml
+autocmd!
+autocmd BufRead,BufNewFile * setlocal spell
+augroup END
+set tabstop=4
+let g:mapleader=' '
\ No newline at end of file
@@ -0,0 +1,2 @@
+vim.cmd("setlocal wrap")
+vim.b.x = 1
\ No newline at end of file
@@ -0,0 +1,62 @@
+
+
+-- File: a.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "x", "y", { silent = true })
+vim.keymap.set({ "v" }, "x", "y", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>ff", "Telescope find_files", { silent = true })
+vim.keymap.set({ "n" }, "<LEADER>fx", "y", { silent = true })
+vim.api.nvim_create_augroup("a_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "BufRead" }, {
+  group = "a_yaml",
+  pattern = { "*.md" },
+  command = "setlocal spell",
+})
+vim.api.nvim_create_autocmd({ "BufRead" }, {
+  group = "a_yaml",
+  pattern = { "*.txt" },
+  command = "setlocal wrap",
+})
+vim.cmd("set number")
+vim.cmd("set wrap")
+vim.g.x = 2
+vim.g.y = 3
+
+
+-- File: b.toml
+
+-- Keybindings:
+vim.keymap.set({ "i" }, "x", "y", { silent = true })
+vim.api.nvim_create_augroup("b_toml", { clear = true })
+vim.api.nvim_create_autocmd({ "BufRead" }, {
+  group = "b_toml",
+  pattern = { "*" },
+  command = "setlocal spell",
+})
+vim.api.nvim_create_autocmd({ "BufRead" }, {
+  group = "b_toml",
+  pattern = { "*" },
+  command = "setlocal wrap",
+})
+vim.cmd("set number")
+vim.cmd("set wrap")
+
+
+-- File: c.json
+
+-- Keybindings:
+vim.keymap.set({ "o" }, "x", "y", { silent = true })
+vim.api.nvim_create_augroup("c_json", { clear = true })
+vim.api.nvim_create_autocmd({ "BufRead" }, {
+  group = "c_json",
+  pattern = { "*" },
+  command = "setlocal spell",
+})
+vim.api.nvim_create_autocmd({ "BufRead" }, {
+  group = "c_json",
+  pattern = { "*" },
+  command = "setlocal wrap",
+})
+vim.cmd("set number")
+vim.cmd("set wrap")
\ No newline at end of file
@@ -0,0 +1,2 @@
+setlocal wrap
+let b:x=1
\ No newline at end of file
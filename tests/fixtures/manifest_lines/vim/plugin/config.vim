@@ -0,0 +1,44 @@
+
+
+" File: a.yaml
+
+" Keybindings:
+nnoremap <silent> x y
+vnoremap <silent> x y
+nnoremap <silent> <LEADER>ff Telescope find_files
+nnoremap <silent> <LEADER>fx y
+augroup a_yaml
+autocmd!
+autocmd BufRead *.md setlocal spell
+autocmd BufRead *.txt setlocal wrap
+augroup END
+set number
+set wrap
+let x=2
+let g:y=3
+
+
+" File: b.toml
+
+" Keybindings:
+inoremap <silent> x y
+augroup b_toml
+autocmd!
+autocmd BufRead * setlocal spell
+autocmd BufRead * setlocal wrap
+augroup END
+set number
+set wrap
+
+
+" File: c.json
+
+" Keybindings:
+onoremap <silent> x y
+augroup c_json
+autocmd!
+autocmd BufRead * setlocal spell
+autocmd BufRead * setlocal wrap
+augroup END
+set number
+set wrap
\ No newline at end of file
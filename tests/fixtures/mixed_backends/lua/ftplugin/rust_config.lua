@@ -0,0 +1 @@
+vim.keymap.set({ "n" }, "K", vim.lsp.buf.hover, { silent = true })
\ No newline at end of file
@@ -0,0 +1,22 @@
+
+
+-- File: callbacks.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<LEADER>f", require('telescope.builtin').find_files, { silent = true })
+vim.api.nvim_create_augroup("yank", { clear = true })
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "yank",
+  pattern = { "*" },
+  callback = function()
+    vim.highlight.on_yank()
+  end,
+})
+
+
+-- File: options.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+vim.cmd("set number")
+vim.opt.tabstop = 4
\ No newline at end of file
@@ -0,0 +1,6 @@
+
+
+" File: vim.toml
+
+" Keybindings:
+nnoremap <silent> <C-l> <C-w>l
\ No newline at end of file
@@ -0,0 +1,14 @@
+
+
+-- File: callbacks.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<LEADER>f", require('telescope.builtin').find_files, { silent = true })
+vim.api.nvim_create_augroup("yank", { clear = true })
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "yank",
+  pattern = { "*" },
+  callback = function()
+    vim.highlight.on_yank()
+  end,
+})
\ No newline at end of file
@@ -0,0 +1,14 @@
+
+
+" File: options.yaml
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+set number
+set tabstop=4
+
+
+" File: vim.toml
+
+" Keybindings:
+nnoremap <silent> <C-l> <C-w>l
\ No newline at end of file
@@ -0,0 +1,33 @@
+
+
+-- File: init.yaml#1
+-- Editor options
+
+-- Keybindings:
+vim.cmd("set number")
+
+
+-- File: init.yaml#2
+-- Mappings
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+
+
+-- File: init.yaml#3
+
+-- Keybindings:
+vim.api.nvim_create_augroup("init_yaml_3", { clear = true })
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "init_yaml_3",
+  pattern = { "*" },
+  callback = function()
+    vim.highlight.on_yank()
+  end,
+})
+
+
+-- File: zz-single.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n", "v" }, "x", "y", { silent = true })
\ No newline at end of file
@@ -0,0 +1,30 @@
+
+
+" File: init.yaml#1
+" Editor options
+
+" Keybindings:
+set number
+
+
+" File: init.yaml#2
+" Mappings
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+
+
+" File: init.yaml#3
+
+" Keybindings:
+augroup init_yaml_3
+autocmd!
+autocmd TextYankPost * lua vim.highlight.on_yank()
+augroup END
+
+
+" File: zz-single.yaml
+
+" Keybindings:
+nnoremap <silent> x y
+vnoremap <silent> x y
\ No newline at end of file
@@ -0,0 +1 @@
+vim.opt_local.shiftwidth = 2
\ No newline at end of file
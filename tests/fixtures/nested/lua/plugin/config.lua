@@ -0,0 +1,23 @@
+
+
+-- File: 00-base.yaml
+
+-- Keybindings:
+vim.cmd("set number")
+
+
+-- File: lang/rust.yaml
+-- Configs can be grouped into subdirectories
+
+-- Keybindings:
+vim.api.nvim_create_augroup("lang_rust_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "BufWritePre" }, {
+  group = "lang_rust_yaml",
+  pattern = { "*.rs" },
+  command = "lua vim.lsp.buf.format()",
+})
+
+
+-- File: lang/web/html.toml
+
+-- Keybindings:
\ No newline at end of file
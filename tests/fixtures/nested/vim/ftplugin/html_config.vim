@@ -0,0 +1 @@
+setlocal shiftwidth=2
\ No newline at end of file
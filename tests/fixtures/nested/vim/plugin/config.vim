@@ -0,0 +1,21 @@
+
+
+" File: 00-base.yaml
+
+" Keybindings:
+set number
+
+
+" File: lang/rust.yaml
+" Configs can be grouped into subdirectories
+
+" Keybindings:
+augroup lang_rust_yaml
+autocmd!
+autocmd BufWritePre *.rs lua vim.lsp.buf.format()
+augroup END
+
+
+" File: lang/web/html.toml
+
+" Keybindings:
\ No newline at end of file
@@ -0,0 +1 @@
+vim.cmd("setlocal expandtab")
\ No newline at end of file
@@ -0,0 +1,6 @@
+
+
+-- File: 00-base.yaml
+
+-- Keybindings:
+vim.cmd("set number")
\ No newline at end of file
@@ -0,0 +1,8 @@
+
+
+-- File: 10-lsp.yaml
+-- Self-contained LSP setup
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "gd", "<CMD>lua vim.lsp.buf.definition()<CR>", { silent = true })
+vim.opt.signcolumn = "yes"
\ No newline at end of file
@@ -0,0 +1,8 @@
+
+
+" File: 10-lsp.yaml
+" Self-contained LSP setup
+
+" Keybindings:
+nnoremap <silent> gd <CMD>lua vim.lsp.buf.definition()<CR>
+set signcolumn=yes
\ No newline at end of file
@@ -0,0 +1,38 @@
+
+
+-- File: 00-base.yaml
+
+-- Keybindings:
+vim.api.nvim_create_augroup("shared", { clear = true })
+vim.api.nvim_create_autocmd({ "BufEnter" }, {
+  group = "shared",
+  pattern = { "*" },
+  command = "echo 'base'",
+})
+vim.cmd("set list")
+vim.opt.tabstop = 4
+vim.opt.colorcolumn = 80
+vim.g.loaded_netrw = 1
+vim.b.undo_ftplugin = "setl list<"
+vim.g.plain = 1
+vim.api.nvim_set_hl(0, "Comment", { italic = true, underline = true })
+vim.api.nvim_set_hl(0, "Normal", { fg = "#ffffff", bg = "black", bold = true })
+
+
+-- File: 99-override.toml
+
+-- Keybindings:
+vim.api.nvim_create_augroup("shared", { clear = false })
+vim.api.nvim_create_autocmd({ "BufLeave" }, {
+  group = "shared",
+  pattern = { "*" },
+  command = "echo 'override'",
+})
+vim.cmd("set colorcolumn&")
+vim.cmd("set list&")
+vim.opt.tabstop = 2
+vim.g.loaded_netrw = nil
+vim.b.undo_ftplugin = nil
+vim.g.plain = nil
+vim.api.nvim_set_hl(0, "Clear", {})
+vim.api.nvim_set_hl(0, "Todo", { link = "Comment" })
\ No newline at end of file
@@ -0,0 +1,33 @@
+
+
+" File: 00-base.yaml
+
+" Keybindings:
+augroup shared
+autocmd!
+autocmd BufEnter * echo 'base'
+augroup END
+set list
+set tabstop=4
+set colorcolumn=80
+let g:loaded_netrw=1
+let b:undo_ftplugin='setl list<'
+let plain=1
+highlight Comment gui=italic,underline
+highlight Normal guifg=#ffffff guibg=black gui=bold
+
+
+" File: 99-override.toml
+
+" Keybindings:
+augroup shared
+autocmd BufLeave * echo 'override'
+augroup END
+set colorcolumn&
+set list&
+set tabstop=2
+unlet! g:loaded_netrw
+unlet! b:undo_ftplugin
+unlet! plain
+highlight clear Clear
+highlight link Todo Comment
\ No newline at end of file
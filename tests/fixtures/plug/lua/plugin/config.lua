@@ -0,0 +1,9 @@
+
+
+-- File: plug.yaml
+
+-- Keybindings:
+vim.keymap.set({ "v" }, "<LEADER>a", "<plug>(EasyAlign)", { remap = true })
+vim.keymap.set({ "v" }, "<LEADER>s", "<Plug>(Surround)", { silent = true, remap = true })
+vim.keymap.set({ "n" }, "ga", "<Plug>(EasyAlign)", { remap = true })
+vim.keymap.set({ "n" }, "gs", "<CMD>Git<CR>", { silent = true })
\ No newline at end of file
@@ -0,0 +1,9 @@
+
+
+" File: plug.yaml
+
+" Keybindings:
+vmap <LEADER>a <plug>(EasyAlign)
+vmap <silent> <LEADER>s <Plug>(Surround)
+nmap ga <Plug>(EasyAlign)
+nnoremap <silent> gs <CMD>Git<CR>
\ No newline at end of file
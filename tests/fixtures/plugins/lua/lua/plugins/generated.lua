@@ -0,0 +1,16 @@
+return {
+  -- File: plugins.yaml
+  {
+    "nvim-telescope/telescope.nvim",
+    lazy = true,
+    event = { "VimEnter" },
+    dependencies = { "nvim-lua/plenary.nvim" },
+    config = function()
+      require("telescope").setup {}
+    end,
+  },
+  {
+    "rust-lang/rust.vim",
+    ft = { "rust" },
+  },
+}
\ No newline at end of file
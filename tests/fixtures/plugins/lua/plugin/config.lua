@@ -0,0 +1,5 @@
+
+
+-- File: plugins.yaml
+
+-- Keybindings:
\ No newline at end of file
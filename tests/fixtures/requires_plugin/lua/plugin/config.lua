@@ -0,0 +1,23 @@
+
+
+-- File: keys.yaml
+
+-- Keybindings:
+vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+if pcall(require, "gitsigns") then
+  vim.keymap.set({ "n", "v" }, "gb", "Gitsigns blame_line", { silent = true })
+  vim.keymap.set({ "n", "v" }, "gh", "Gitsigns preview_hunk", { silent = true })
+end
+if pcall(require, "it's\"quoted") then
+  vim.keymap.set({ "n" }, "<LEADER>q", "quit", { silent = true })
+end
+-- find
+if pcall(require, "telescope") then
+  vim.keymap.set({ "n" }, "<LEADER>ff", "<CMD>Telescope find_files<CR>", { silent = true })
+  vim.keymap.set({ "n" }, "<LEADER>fg", "<CMD>Telescope live_grep<CR>", { silent = true })
+  pcall(function()
+    require("which-key").add({
+      { "<LEADER>f", group = "find", mode = { "n" } },
+    })
+  end)
+end
\ No newline at end of file
@@ -0,0 +1,20 @@
+
+
+" File: keys.yaml
+
+" Keybindings:
+nnoremap <silent> <C-h> <C-w>h
+if luaeval('pcall(require, "gitsigns")')
+nnoremap <silent> gb Gitsigns blame_line
+vnoremap <silent> gb Gitsigns blame_line
+nnoremap <silent> gh Gitsigns preview_hunk
+vnoremap <silent> gh Gitsigns preview_hunk
+endif
+if luaeval('pcall(require, "it''s\"quoted")')
+nnoremap <silent> <LEADER>q quit
+endif
+" find
+if luaeval('pcall(require, "telescope")')
+nnoremap <silent> <LEADER>ff <CMD>Telescope find_files<CR>
+nnoremap <silent> <LEADER>fg <CMD>Telescope live_grep<CR>
+endif
\ No newline at end of file
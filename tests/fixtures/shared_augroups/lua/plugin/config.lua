@@ -0,0 +1,44 @@
+
+
+-- File: 10-base.yaml
+
+-- Keybindings:
+vim.api.nvim_create_augroup("10_base_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "TextYankPost" }, {
+  group = "10_base_yaml",
+  pattern = { "*" },
+  callback = function()
+    vim.highlight.on_yank()
+  end,
+})
+
+
+-- File: 20-lang.yaml
+
+-- Keybindings:
+
+
+-- Augroups shared between files:
+vim.api.nvim_create_augroup("formatting", { clear = true })
+-- From 10-base.yaml
+vim.api.nvim_create_autocmd({ "BufWritePre" }, {
+  group = "formatting",
+  pattern = { "*.rs" },
+  command = "lua vim.lsp.buf.format()",
+})
+-- From 20-lang.yaml
+if vim.fn.has("nvim-0.10") == 1 then
+  vim.api.nvim_create_autocmd({ "BufWritePre" }, {
+    group = "formatting",
+    pattern = { "*.go" },
+    desc = "Format go",
+    command = "lua vim.lsp.buf.format()",
+  })
+end
+if vim.fn.has("nvim-0.10") == 1 then
+  vim.api.nvim_create_autocmd({ "BufWritePre" }, {
+    group = "formatting",
+    pattern = { "*.py" },
+    command = "%s/\\s\\+$//e",
+  })
+end
\ No newline at end of file
@@ -0,0 +1,30 @@
+
+
+" File: 10-base.yaml
+
+" Keybindings:
+augroup 10_base_yaml
+autocmd!
+autocmd TextYankPost * lua vim.highlight.on_yank()
+augroup END
+
+
+" File: 20-lang.yaml
+
+" Keybindings:
+
+
+" Augroups shared between files:
+augroup formatting
+autocmd!
+" From 10-base.yaml
+autocmd BufWritePre *.rs lua vim.lsp.buf.format()
+" From 20-lang.yaml
+if has('nvim-0.10')
+" Format go
+autocmd BufWritePre *.go lua vim.lsp.buf.format()
+endif
+if has('nvim-0.10')
+autocmd BufWritePre *.py %s/\s\+$//e
+endif
+augroup END
\ No newline at end of file
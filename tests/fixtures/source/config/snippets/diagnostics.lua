@@ -0,0 +1 @@
+vim.diagnostic.config({ virtual_text = false })
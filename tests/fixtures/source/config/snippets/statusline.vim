@@ -0,0 +1,4 @@
+function! StatusMode() abort
+  return mode() ==# 'n' ? 'NORMAL' : 'OTHER'
+endfunction
+set statusline=%{StatusMode()}\ %f
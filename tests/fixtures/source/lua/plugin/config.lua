@@ -0,0 +1,15 @@
+
+
+-- File: source.yaml
+
+-- Keybindings:
+vim.cmd("set number")
+-- Source: snippets/statusline.vim
+vim.cmd([[
+function! StatusMode() abort
+  return mode() ==# 'n' ? 'NORMAL' : 'OTHER'
+endfunction
+set statusline=%{StatusMode()}\ %f
+]])
+-- Source: snippets/diagnostics.lua
+vim.diagnostic.config({ virtual_text = false })
\ No newline at end of file
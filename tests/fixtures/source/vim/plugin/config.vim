@@ -0,0 +1,15 @@
+
+
+" File: source.yaml
+
+" Keybindings:
+set number
+" Source: snippets/statusline.vim
+function! StatusMode() abort
+  return mode() ==# 'n' ? 'NORMAL' : 'OTHER'
+endfunction
+set statusline=%{StatusMode()}\ %f
+" Source: snippets/diagnostics.lua
+lua << EOF
+vim.diagnostic.config({ virtual_text = false })
+EOF
\ No newline at end of file
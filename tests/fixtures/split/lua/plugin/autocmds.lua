@@ -0,0 +1,9 @@
+
+
+-- File: 00-base.yaml
+vim.api.nvim_create_augroup("00_base_yaml", { clear = true })
+vim.api.nvim_create_autocmd({ "BufWritePre" }, {
+  group = "00_base_yaml",
+  pattern = { "*" },
+  command = "%s/\\s\\+$//e",
+})
\ No newline at end of file
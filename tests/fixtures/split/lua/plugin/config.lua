@@ -0,0 +1,8 @@
+
+
+-- File: 00-base.yaml
+vim.g.mapleader = " "
+vim.api.nvim_set_hl(0, "Comment", { italic = true })
+
+
+-- File: 10-runtime.yaml
\ No newline at end of file
@@ -0,0 +1,11 @@
+
+
+-- File: 00-base.yaml
+vim.keymap.set({ "n" }, "<LEADER>w", ":w<CR>", { silent = true })
+vim.keymap.set({ "ia" }, "teh", "the", {})
+
+
+-- File: 10-runtime.yaml
+if vim.fn.has("nvim-0.10.0") == 1 then
+  vim.keymap.set({ "n" }, "<C-h>", "<C-w>h", { silent = true })
+end
\ No newline at end of file
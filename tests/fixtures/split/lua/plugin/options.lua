@@ -0,0 +1,11 @@
+
+
+-- File: 00-base.yaml
+vim.cmd("set number")
+vim.opt.tabstop = 4
+
+
+-- File: 10-runtime.yaml
+if vim.fn.has("nvim-0.10.0") == 1 then
+  vim.cmd("set termguicolors")
+end
\ No newline at end of file
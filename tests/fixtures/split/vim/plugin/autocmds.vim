@@ -0,0 +1,7 @@
+
+
+" File: 00-base.yaml
+augroup 00_base_yaml
+autocmd!
+autocmd BufWritePre * %s/\s\+$//e
+augroup END
\ No newline at end of file
@@ -0,0 +1,8 @@
+
+
+" File: 00-base.yaml
+let mapleader=' '
+highlight Comment gui=italic
+
+
+" File: 10-runtime.yaml
\ No newline at end of file
@@ -0,0 +1,11 @@
+
+
+" File: 00-base.yaml
+nnoremap <silent> <LEADER>w :w<CR>
+inoreabbrev teh the
+
+
+" File: 10-runtime.yaml
+if has('nvim-0.10.0')
+nnoremap <silent> <C-h> <C-w>h
+endif
\ No newline at end of file
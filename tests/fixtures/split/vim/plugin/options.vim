@@ -0,0 +1,11 @@
+
+
+" File: 00-base.yaml
+set number
+set tabstop=4
+
+
+" File: 10-runtime.yaml
+if has('nvim-0.10.0')
+set termguicolors
+endif
\ No newline at end of file
@@ -0,0 +1,9 @@
+
+
+-- File: statusline.yaml
+-- Options with spaces, `%` items and other characters `set` needs escaped
+
+-- Keybindings:
+vim.opt.statusline = "%f %m%= %{&fileencoding} | %l:%c %P"
+vim.opt.winbar = "%#WinBar# %t %{get(b:, 'git_branch', '')}"
+vim.opt.titlestring = "C:\\Users\\%{$USER}"
\ No newline at end of file
@@ -0,0 +1,9 @@
+
+
+" File: statusline.yaml
+" Options with spaces, `%` items and other characters `set` needs escaped
+
+" Keybindings:
+set statusline=%f\ %m%=\ %{&fileencoding}\ \|\ %l:%c\ %P
+set winbar=%#WinBar#\ %t\ %{get(b:,\ 'git_branch',\ '')}
+set titlestring=C:\\Users\\%{$USER}
\ No newline at end of file
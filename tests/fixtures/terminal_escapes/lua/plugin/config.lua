@@ -0,0 +1,12 @@
+
+
+-- File: terminal.yaml
+
+-- Keybindings:
+vim.keymap.set({ "i" }, "<C-o>",  "<C-\\><C-o>", { silent = true })
+vim.keymap.set({ "n" }, "gt",     "<C-\\><C-n>", {})
+vim.keymap.set({ "t" }, "<C-\\>", "<C-\\><C-\\>", { silent = true })
+vim.keymap.set({ "t" }, "<C-w>h", "<C-\\><C-n><C-w>h", { silent = true })
+vim.keymap.set({ "t" }, "<Esc>",  "<C-\\><C-n>", { silent = true })
+vim.keymap.set({ "t" }, "<C-q>",  "<CMD>stopinsert<CR>", { silent = true })
+vim.keymap.set({ "t" }, "<C-l>",  "<C-\\><C-n>:ls|echo 'done'<CR>", {})
\ No newline at end of file
@@ -0,0 +1,12 @@
+
+
+" File: terminal.yaml
+
+" Keybindings:
+inoremap <silent> <C-o>  <C-\><C-o>
+nnoremap          gt     <C-\><C-n>
+tnoremap <silent> <C-\>  <C-\><C-\>
+tnoremap <silent> <C-w>h <C-\><C-n><C-w>h
+tnoremap <silent> <Esc>  <C-\><C-n>
+tnoremap <silent> <C-q>  <CMD>stopinsert<CR>
+tnoremap          <C-l>  <C-\><C-n>:ls\|echo 'done'<CR>
\ No newline at end of file
@@ -0,0 +1,80 @@
+//! The file type flag `f` takes the file type from the label, so `nf_rust_label` maps in normal
+//! mode for rust with the label `label`. Every way of combining them is checked here, the other
+//! flags are covered by the fixtures.
+
+use std::{fs, path::PathBuf};
+
+use nvim_config::{generate, parse_config, Backend, Error, GeneratedFiles, Options};
+
+/// Generates a config mapping `x` to `y` with `flags`.
+fn generate_flags(flags: &str) -> anyhow::Result<GeneratedFiles> {
+    let dir: PathBuf = std::env::temp_dir().join(format!(
+        "nvim-config-flags-{}-{}",
+        std::process::id(),
+        flags
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("keys.yaml");
+    fs::write(&path, format!("keys:\n  {}:\n    x: y\n", flags)).unwrap();
+    let config = parse_config(&path);
+    fs::remove_dir_all(&dir).unwrap();
+    let options = Options::default();
+    generate(&[config?], &options)
+}
+
+/// The file type file generated for `flags` and its content.
+fn file_type_mapping(flags: &str) -> (String, String) {
+    let generated = generate_flags(flags).unwrap();
+    let mut file_types: Vec<_> = generated
+        .files
+        .into_iter()
+        .filter_map(|((_, file_type), content)| Some((file_type?, content)))
+        .collect();
+    assert_eq!(file_types.len(), 1, "{}", flags);
+    file_types.remove(0)
+}
+
+#[test]
+fn file_type() {
+    for flags in ["nf_rust", "fn_rust"] {
+        let (file_type, content) = file_type_mapping(flags);
+        assert_eq!(file_type, "rust");
+        assert!(content.contains("nnoremap <silent> x y"), "{}", content);
+    }
+}
+
+#[test]
+fn file_type_with_label() {
+    for (flags, label) in [("nf_rust_label", "label"), ("nf_rust_my_label", "my_label")] {
+        let (file_type, content) = file_type_mapping(flags);
+        assert_eq!(file_type, "rust");
+        assert!(content.contains(&format!("\" {}\n", label)), "{}", content);
+    }
+}
+
+#[test]
+fn without_file_type() {
+    let generated = generate_flags("n_rust").unwrap();
+    assert_eq!(generated.files.len(), 1);
+    assert!(generated.files[&(Backend::Vim, None)].contains("\" rust\n"));
+}
+
+#[test]
+fn invalid_file_type() {
+    for (flags, message) in [
+        ("nf", "Filetype flag only supported when filetype is given"),
+        ("f", "Filetype flag only supported when filetype is given"),
+        ("nff_rust", "Duplicate filetype flag not supported"),
+        ("nff_rust_label", "Duplicate filetype flag not supported"),
+        ("nf_", "Filetype flag needs a filetype after the `_`"),
+        ("nf__label", "Filetype flag needs a filetype after the `_`"),
+    ] {
+        let error = generate_flags(flags).err().unwrap();
+        match error.downcast_ref::<Error>() {
+            Some(Error::Flag { flag, .. }) => assert_eq!(flag, flags),
+            other => panic!("Expected a flag error for `{}`, got {:?}", flags, other),
+        }
+        let error = format!("{:?}", error);
+        assert!(error.contains(message), "{}", error);
+    }
+}
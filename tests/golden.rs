@@ -0,0 +1,182 @@
+//! Runs every fixture in `tests/fixtures` and compares the output with the golden files next to
+//! its `config` dir: `vim/` and `lua/` hold the generated files of each backend, `error.txt` the
+//! error of fixtures that are expected to fail and `args.txt` the flags passed (like `--split`,
+//! `--manifest`, `--format` or `--group-leader`). Run with `UPDATE_GOLDEN=1` to overwrite them
+//! with the current output.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use nvim_config::{generate, parse_dir, Backend, Options};
+
+/// The generated files as paths relative to the output dir, or the error.
+fn output(
+    config_dir: &Path,
+    backend: Backend,
+    args: &[String],
+) -> Result<BTreeMap<PathBuf, String>, String> {
+    let options = Options {
+        backend,
+        split: args.iter().any(|arg| arg == "--split"),
+        manifest: args.iter().any(|arg| arg == "--manifest"),
+        format: args.iter().any(|arg| arg == "--format"),
+        group_leader: args.iter().any(|arg| arg == "--group-leader"),
+        annotate: args.iter().any(|arg| arg == "--annotate"),
+        ..Options::default()
+    };
+    let generated = parse_dir(config_dir)
+        .and_then(|configs| generate(&configs, &options))
+        .map_err(|error| format!("{:?}\n", error))?;
+    let mut files: BTreeMap<_, _> = generated
+        .files
+        .into_iter()
+        .map(|((backend, file_type), content)| {
+            let path = match file_type {
+                None => Path::new("plugin").join(format!("config.{}", backend.extension())),
+                Some(file_type) => Path::new("ftplugin").join(format!(
+                    "{}_config.{}",
+                    file_type,
+                    backend.extension()
+                )),
+            };
+            (path, content)
+        })
+        .collect();
+    for ((backend, name), content) in generated.plugin_files {
+        let path = Path::new("plugin").join(format!("{}.{}", name, backend.extension()));
+        files.insert(path, content);
+    }
+    if let Some(manifest) = generated.manifest {
+        files.insert(
+            Path::new("plugin/.nvim-config-manifest.json").into(),
+            manifest,
+        );
+    }
+    if let Some(plugins) = generated.plugins {
+        files.insert(Path::new("lua/plugins/generated.lua").into(), plugins);
+    }
+    Ok(files)
+}
+
+/// All files below `dir` as paths relative to `dir`.
+fn read_tree(dir: &Path) -> BTreeMap<PathBuf, String> {
+    fn visit(root: &Path, dir: &Path, files: &mut BTreeMap<PathBuf, String>) {
+        for entry in fs::read_dir(dir).into_iter().flatten() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                visit(root, &path, files);
+            } else {
+                let content = fs::read_to_string(&path).unwrap();
+                files.insert(path.strip_prefix(root).unwrap().to_owned(), content);
+            }
+        }
+    }
+    let mut files = BTreeMap::new();
+    visit(dir, dir, &mut files);
+    files
+}
+
+fn write_tree(dir: &Path, files: &BTreeMap<PathBuf, String>) {
+    if dir.exists() {
+        fs::remove_dir_all(dir).unwrap();
+    }
+    for (path, content) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
+/// Compares `actual` with `expected`, describing every difference.
+fn compare(
+    fixture: &str,
+    expected: &BTreeMap<PathBuf, String>,
+    actual: &BTreeMap<PathBuf, String>,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+    for (path, content) in actual {
+        match expected.get(path) {
+            None => failures.push(format!("{}: unexpected {}", fixture, path.display())),
+            Some(expected) if expected != content => failures.push(format!(
+                "{}: {} differs\n--- expected\n{}\n--- actual\n{}",
+                fixture,
+                path.display(),
+                expected,
+                content
+            )),
+            Some(_) => {}
+        }
+    }
+    for path in expected.keys().filter(|path| !actual.contains_key(*path)) {
+        failures.push(format!("{}: missing {}", fixture, path.display()));
+    }
+    failures
+}
+
+#[test]
+fn golden() {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut fixture_dirs: Vec<_> = fs::read_dir(&fixtures)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    fixture_dirs.sort();
+    assert!(!fixture_dirs.is_empty(), "No fixtures found");
+
+    let mut failures = Vec::new();
+    for dir in fixture_dirs {
+        let fixture = dir.file_name().unwrap().to_string_lossy().to_string();
+        let config_dir = dir.join("config");
+        let error_file = dir.join("error.txt");
+        let args: Vec<_> = fs::read_to_string(dir.join("args.txt"))
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        if error_file.exists() {
+            let actual = match output(&config_dir, Backend::Vim, &args) {
+                Ok(_) => String::new(),
+                Err(error) => error,
+            };
+            if update {
+                fs::write(&error_file, &actual).unwrap();
+            } else if fs::read_to_string(&error_file).unwrap() != actual {
+                failures.push(format!(
+                    "{}: expected error\n{}\n--- actual\n{}",
+                    fixture,
+                    fs::read_to_string(&error_file).unwrap(),
+                    actual
+                ));
+            }
+            continue;
+        }
+        for (backend, name) in [(Backend::Vim, "vim"), (Backend::Lua, "lua")] {
+            let expected_dir = dir.join(name);
+            let actual = match output(&config_dir, backend, &args) {
+                Ok(files) => files,
+                Err(error) => {
+                    failures.push(format!("{} ({}): failed with {}", fixture, name, error));
+                    continue;
+                }
+            };
+            if update {
+                write_tree(&expected_dir, &actual);
+            } else {
+                failures.extend(compare(
+                    &format!("{} ({})", fixture, name),
+                    &read_tree(&expected_dir),
+                    &actual,
+                ));
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{}\n\nRun with UPDATE_GOLDEN=1 to accept the new output",
+        failures.join("\n\n")
+    );
+}
@@ -0,0 +1,96 @@
+//! `--import` turns generated vimscript back into a config that generates the same commands.
+
+use std::{fs, path::Path};
+
+use nvim_config::{generate, import_vim, parse_str, Backend, Options};
+
+/// The commands of `vim` without comments and augroups, sorted as the order of mappings changes.
+fn commands(vim: &str) -> Vec<&str> {
+    let mut commands: Vec<_> = vim
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            !(line.is_empty()
+                || line.starts_with('"')
+                || line.starts_with("augroup")
+                || *line == "autocmd!")
+        })
+        .collect();
+    commands.sort_unstable();
+    commands
+}
+
+#[test]
+fn round_trip() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    for fixture in [
+        "keys",
+        "expressions",
+        "plug",
+        "default_map_flags",
+        "errorformat",
+        "let_values",
+    ] {
+        let path = fixtures.join(fixture).join("vim/plugin/config.vim");
+        let vim = fs::read_to_string(&path).unwrap();
+        let import = import_vim(&vim);
+        assert_eq!(import.skipped, [], "{}", fixture);
+
+        let config = parse_str(&import.config, "yaml", "import.yaml")
+            .unwrap_or_else(|error| panic!("{}: {:?}\n{}", fixture, error, import.config));
+        let options = Options::default();
+        let mut generated = generate(&[config], &options).unwrap();
+        let regenerated = generated.files.remove(&(Backend::Vim, None)).unwrap();
+        assert_eq!(
+            commands(&regenerated),
+            commands(&vim),
+            "{}\n{}",
+            fixture,
+            import.config
+        );
+    }
+}
+
+#[test]
+fn skipped_lines() {
+    let import = import_vim(
+        "\" Settings\n\
+         set number\n\
+         xnoremap p \"_dP\n\
+         let g:colors = [\n\
+         \\ 'dark',\n\
+         \\ ]\n\
+         function! Foo()\n\
+         \x20 let l:x = 1\n\
+         endfunction\n\
+         set shiftwidth+=2\n",
+    );
+    assert_eq!(
+        import.config,
+        "set:\n  - number\nlet:\n  \"g:colors\":\n    expr: \"[ 'dark', ]\"\n"
+    );
+    assert_eq!(
+        import.skipped,
+        [
+            (3, "xnoremap p \"_dP".to_string()),
+            (7, "function! Foo()".to_string()),
+            (10, "set shiftwidth+=2".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn leader_mappings() {
+    let import = import_vim(
+        "nnoremap f :echo 1<CR>\n\
+         nnoremap <leader>f :echo 2<CR>\n\
+         nnoremap <LocalLeader>f :echo 3<CR>\n\
+         nnoremap <Leader>f :echo 4<CR>\n",
+    );
+    assert_eq!(import.skipped, []);
+    assert_eq!(
+        import.config,
+        "keys:\n  nu:\n    f: \":echo 1<CR>\"\n  nlu:\n    f: \":echo 4<CR>\"\n  nku:\n    f: \
+         \":echo 3<CR>\"\n"
+    );
+}
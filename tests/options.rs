@@ -0,0 +1,51 @@
+//! Boolean options are toggled (`set nowrap`), as `set wrap=no` is no valid vimscript, other
+//! values are assigned (`set shiftwidth=4`).
+
+use nvim_config::{generate, parse_str, Backend, Options};
+
+fn generate_options(source: &str, backend: Backend) -> Vec<String> {
+    let config = parse_str(source, "yaml", "options.yaml").unwrap();
+    let options = Options {
+        backend,
+        strict: true,
+        ..Options::default()
+    };
+    let mut generated = generate(&[config], &options).unwrap();
+    generated
+        .files
+        .remove(&(backend, None))
+        .unwrap()
+        .lines()
+        .filter(|line| line.starts_with("set ") || line.starts_with("vim."))
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn booleans_and_values() {
+    let source = "set: [nowrap, number]\n\
+                  set_value:\n  spell: true\n  list: false\n  shiftwidth: 4\n  \
+                  listchars: 'tab:> ,trail:-'\n";
+    assert_eq!(
+        generate_options(source, Backend::Vim),
+        [
+            "set nowrap",
+            "set number",
+            "set spell",
+            "set nolist",
+            "set shiftwidth=4",
+            "set listchars=tab:>\\ ,trail:-",
+        ]
+    );
+    assert_eq!(
+        generate_options(source, Backend::Lua),
+        [
+            "vim.cmd(\"set nowrap\")",
+            "vim.cmd(\"set number\")",
+            "vim.opt.spell = true",
+            "vim.opt.list = false",
+            "vim.opt.shiftwidth = 4",
+            "vim.opt.listchars = \"tab:> ,trail:-\"",
+        ]
+    );
+}
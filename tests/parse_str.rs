@@ -0,0 +1,59 @@
+//! Configs that are not files, like the one of `--stdin`, parse the same as files.
+
+use nvim_config::{generate, parse_str, Backend, Options};
+
+fn generate_vim(source: &str, format: &str) -> String {
+    let config = parse_str(source, format, "stdin").unwrap();
+    let options = Options {
+        strict: true,
+        ..Options::default()
+    };
+    let mut generated = generate(&[config], &options).unwrap();
+    generated.files.remove(&(Backend::Vim, None)).unwrap()
+}
+
+#[test]
+fn formats() {
+    let yaml = generate_vim("keys:\n  n:\n    <C-s>: :w<CR>\nset: number\n", "yaml");
+    assert!(yaml.contains("\" File: stdin\n"), "{}", yaml);
+    assert!(
+        yaml.contains("nnoremap <silent> <C-s> :w<CR>\n"),
+        "{}",
+        yaml
+    );
+    assert!(yaml.contains("set number"), "{}", yaml);
+    let toml = generate_vim(
+        "set = \"number\"\n[keys.n]\n\"<C-s>\" = \":w<CR>\"\n",
+        "TOML",
+    );
+    assert_eq!(yaml, toml);
+    let json = generate_vim(
+        r#"{"keys": {"n": {"<C-s>": ":w<CR>"}}, "set": "number"}"#,
+        "json",
+    );
+    assert_eq!(yaml, json);
+}
+
+#[test]
+fn unsupported_format() {
+    let error = parse_str("set: number", "ini", "stdin").err().unwrap();
+    assert_eq!(error.to_string(), "Unsupported config file: stdin");
+}
+
+#[test]
+fn json_syntax() {
+    // Valid JSON that is no valid YAML
+    let vim = generate_vim("{\"let\":\t{\"g:path\": \"a\\/b\"}}", "json");
+    assert!(vim.contains("let g:path='a/b'"), "{}", vim);
+
+    let error = parse_str("{\n  \"set\": \"number\",\n}", "json", "stdin")
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "Failed to parse file: stdin:3:1\n   3 | }\n     | ^"
+    );
+    for invalid in ["{\"set\": 'number'}", "{\"set\": 01}", "[\"\\x\"]", "{} {}"] {
+        assert!(parse_str(invalid, "json", "stdin").is_err(), "{}", invalid);
+    }
+}